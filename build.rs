@@ -0,0 +1,23 @@
+use std::{env, process::Command};
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rustc-check-cfg=cfg(todo_or_die_nightly_diagnostics)");
+
+    // `nightly-diagnostics` does nothing on stable, so `--all-features` keeps working there
+    if env::var_os("CARGO_FEATURE_NIGHTLY_DIAGNOSTICS").is_some() && is_nightly() {
+        println!("cargo:rustc-cfg=todo_or_die_nightly_diagnostics");
+    }
+}
+
+fn is_nightly() -> bool {
+    let rustc = env::var_os("RUSTC").unwrap_or_else(|| "rustc".into());
+    Command::new(rustc)
+        .arg("--version")
+        .output()
+        .map(|output| {
+            let version = String::from_utf8_lossy(&output.stdout);
+            version.contains("-nightly") || version.contains("-dev")
+        })
+        .unwrap_or(false)
+}
@@ -0,0 +1,135 @@
+use crate::{http::request, package};
+use anyhow::{Context as _, Result};
+use hyper::Request;
+use serde::Deserialize;
+use syn::parse::Parse;
+
+pub(crate) fn archlinux(input: Input) -> Result<Option<String>> {
+    let (registry, versions) = if input.aur {
+        ("the AUR", aur_versions(&input.package.name)?)
+    } else {
+        ("Arch Linux", official_versions(&input.package.name)?)
+    };
+
+    if versions.is_empty() {
+        return Err(package::NotInRegistry {
+            name: input.package.name,
+            registry: registry.to_owned(),
+        }
+        .into());
+    }
+
+    let latest_version = versions
+        .iter()
+        .filter_map(|version| package::parse_version(strip_epoch(version)))
+        .max()
+        .context("No valid versions found for package")?;
+
+    Ok(package::check(&input.package, registry, &latest_version))
+}
+
+fn official_versions(name: &str) -> Result<Vec<String>> {
+    #[derive(Debug, Deserialize)]
+    struct Response {
+        results: Vec<Package>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Package {
+        pkgname: String,
+        pkgver: String,
+    }
+
+    let data = request::<Response>(
+        Request::builder()
+            .uri(format!(
+                "https://archlinux.org/packages/search/json/?name={}",
+                name
+            ))
+            .body(())
+            .unwrap(),
+    )?;
+
+    Ok(data
+        .results
+        .into_iter()
+        .filter(|package| package.pkgname == name)
+        .map(|package| package.pkgver)
+        .collect())
+}
+
+fn aur_versions(name: &str) -> Result<Vec<String>> {
+    #[derive(Debug, Deserialize)]
+    struct Response {
+        results: Vec<Package>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Package {
+        #[serde(rename = "Version")]
+        version: String,
+    }
+
+    let data = request::<Response>(
+        Request::builder()
+            .uri(format!("https://aur.archlinux.org/rpc/v5/info/{}", name))
+            .body(())
+            .unwrap(),
+    )?;
+
+    Ok(data
+        .results
+        .into_iter()
+        .map(|package| package.version)
+        .collect())
+}
+
+/// Remove the epoch from versions like `1:2.3.4`.
+fn strip_epoch(version: &str) -> &str {
+    version
+        .split_once(':')
+        .map_or(version, |(_, version)| version)
+}
+
+pub(crate) struct Input {
+    package: package::Input,
+    aur: bool,
+}
+
+impl Parse for Input {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let package = input.parse::<package::Input>()?;
+
+        let mut aur = false;
+        if !input.is_empty() {
+            let ident = input.parse::<syn::Ident>()?;
+            if ident != "aur" {
+                return Err(syn::Error::new(ident.span(), "expected `aur`"));
+            }
+            aur = true;
+
+            input.parse::<syn::token::Comma>().ok();
+        }
+
+        Ok(Self { package, aur })
+    }
+}
+
+/// ```compile_fail
+/// todo_or_die::archlinux!("openssl", ">=3.0");
+/// ```
+///
+/// ```
+/// todo_or_die::archlinux!("openssl", ">=1000");
+/// ```
+///
+/// ```compile_fail
+/// todo_or_die::archlinux!("yay", ">=10", aur);
+/// ```
+///
+/// unknown package
+/// ```compile_fail
+/// todo_or_die::archlinux!("no-such-package-todo-or-die", ">=0");
+/// ```
+#[allow(dead_code)]
+fn tests() {}
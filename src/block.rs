@@ -0,0 +1,452 @@
+use crate::{
+    combinators::{self, Condition},
+    Outcome,
+};
+use proc_macro2::{Delimiter, Span, TokenStream, TokenTree};
+use quote::quote;
+use syn::parse::Parse;
+
+/// Expand `remove_code! { when: ...; ... }`, passing the code through unchanged along with the
+/// compile error if the condition holds.
+pub(crate) fn remove_code(input: TokenStream) -> TokenStream {
+    let (condition, code) = match split_condition(input)
+        .and_then(|(condition, code)| Ok((strip_label(condition, "when")?, code)))
+    {
+        Ok(value) => value,
+        Err(err) => return err.to_compile_error(),
+    };
+
+    let mut tokens = TokenStream::from(crate::perform_check_with(
+        condition.into(),
+        |condition: Condition, _| combinators::on_item(condition, "this code should be removed"),
+    ));
+    tokens.extend(code);
+    tokens
+}
+
+/// Expand `stub_fn! { when: ...; fn ... }`, passing the function through unchanged along with
+/// the compile error if the condition holds.
+pub(crate) fn stub_fn(input: TokenStream) -> TokenStream {
+    let (condition, code) = match split_condition(input)
+        .and_then(|(condition, code)| Ok((strip_label(condition, "when")?, code)))
+    {
+        Ok(value) => value,
+        Err(err) => return err.to_compile_error(),
+    };
+
+    let (description, span) = crate::item::describe(&code);
+    if !description.starts_with("fn ") || !is_single_item(&code) {
+        let mut tokens = syn::Error::new(
+            code.clone()
+                .into_iter()
+                .next()
+                .map_or(span, |token| token.span()),
+            "expected a single function",
+        )
+        .to_compile_error();
+        tokens.extend(code);
+        return tokens;
+    }
+
+    let mut tokens = crate::item::check(
+        condition,
+        format!("`{}` is a stub and should be replaced", description),
+        span,
+    );
+    tokens.extend(code);
+    tokens
+}
+
+/// Whether the tokens are a single item with a body, such as a function.
+fn is_single_item(tokens: &TokenStream) -> bool {
+    let tokens = tokens.clone().into_iter().collect::<Vec<_>>();
+    let bodies = tokens
+        .iter()
+        .filter(|token| matches!(token, TokenTree::Group(group) if group.delimiter() == Delimiter::Brace))
+        .count();
+    bodies == 1
+        && matches!(tokens.last(), Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace)
+}
+
+/// Expand `keep_until! { ...; ... }`, passing the code through unchanged until the condition
+/// holds and then omitting it with a warning.
+pub(crate) fn keep_until(input: TokenStream) -> TokenStream {
+    let (condition, code) = match split_condition(input) {
+        Ok(value) => value,
+        Err(err) => return err.to_compile_error(),
+    };
+
+    let mut expired = false;
+    let mut tokens = TokenStream::from(crate::perform_check_with(
+        condition.into(),
+        |condition: Condition, modifiers| {
+            if modifiers.is_conditional() {
+                return Err(syn::Error::new(
+                    Span::call_site(),
+                    "`only`, `only_release`, and `only_debug` aren't supported by `keep_until!`",
+                )
+                .into());
+            }
+
+            Ok(
+                match combinators::on_item(condition, "this code is no longer compiled")? {
+                    Some(msg) => {
+                        expired = true;
+                        Outcome::Warn(msg)
+                    }
+                    None => Outcome::Pass,
+                },
+            )
+        },
+    ));
+
+    if !expired {
+        tokens.extend(code);
+    }
+    tokens
+}
+
+/// Expand `todo_until!(condition, ...)` into `todo!(...)`, along with the compile error if the
+/// condition holds.
+pub(crate) fn todo_until(input: TokenStream) -> TokenStream {
+    placeholder_until(input, "todo")
+}
+
+/// Expand `unimplemented_until!(condition, ...)` into `unimplemented!(...)`, along with the
+/// compile error if the condition holds.
+pub(crate) fn unimplemented_until(input: TokenStream) -> TokenStream {
+    placeholder_until(input, "unimplemented")
+}
+
+/// Expand a placeholder macro, such as `todo!`, that turns into a compile error once the
+/// condition holds.
+fn placeholder_until(input: TokenStream, name: &str) -> TokenStream {
+    // the arguments are needed even if the check is skipped
+    let args = match crate::modifiers::Modifiers::split(input.clone())
+        .and_then(|(input, _)| syn::parse2::<PlaceholderUntil>(input))
+    {
+        Ok(input) => input.args,
+        Err(err) => return err.to_compile_error(),
+    };
+
+    let description = match args.clone().into_iter().next() {
+        Some(TokenTree::Literal(lit)) => format!("`{}!({})` should be done", name, lit),
+        _ => format!("`{}!()` should be done", name),
+    };
+
+    let check = TokenStream::from(crate::perform_check_with(
+        input.into(),
+        |input: PlaceholderUntil, _| combinators::on_item(input.condition, &description),
+    ));
+
+    let name = syn::Ident::new(name, Span::call_site());
+    quote! {
+        {
+            #check
+            ::std::#name!(#args)
+        }
+    }
+}
+
+/// A condition followed by the arguments to a placeholder macro, such as
+/// `date(2025, 12, 1), "implement pagination"`.
+struct PlaceholderUntil {
+    condition: Condition,
+    args: TokenStream,
+}
+
+impl Parse for PlaceholderUntil {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let condition = input.parse()?;
+        let args = if input.parse::<syn::token::Comma>().is_ok() {
+            input.parse()?
+        } else if input.is_empty() {
+            TokenStream::new()
+        } else {
+            return Err(input.error("expected `,`"));
+        };
+        Ok(Self { condition, args })
+    }
+}
+
+/// Split `condition; code` at the first `;`.
+fn split_condition(input: TokenStream) -> syn::Result<(TokenStream, TokenStream)> {
+    let mut tokens = input.into_iter();
+    let condition = tokens
+        .by_ref()
+        .take_while(|token| !matches!(token, TokenTree::Punct(punct) if punct.as_char() == ';'))
+        .collect::<TokenStream>();
+    let code = tokens.collect::<TokenStream>();
+
+    if condition.is_empty() {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "expected a condition followed by `;`",
+        ));
+    }
+
+    Ok((condition, code))
+}
+
+/// Remove `label:` from the start of the tokens.
+fn strip_label(tokens: TokenStream, label: &str) -> syn::Result<TokenStream> {
+    let mut tokens = tokens.into_iter().collect::<Vec<_>>();
+    match &tokens[..] {
+        [ident, colon, ..] if crate::modifiers::is_labeled(ident, colon, label) => {
+            tokens.drain(..2);
+            Ok(tokens.into_iter().collect())
+        }
+        _ => Err(syn::Error::new(
+            tokens.first().map_or_else(Span::call_site, TokenTree::span),
+            format!("expected `{}:`", label),
+        )),
+    }
+}
+
+/// ```compile_fail
+/// todo_or_die::remove_code! {
+///     when: after_date(1990, 01, 01);
+///     fn retry_shim() {}
+/// }
+/// ```
+///
+/// ```
+/// todo_or_die::remove_code! {
+///     when: after_date(3000, 01, 01);
+///     fn retry_shim() -> u32 { 1 }
+///     struct Legacy;
+/// }
+///
+/// assert_eq!(retry_shim(), 1);
+/// let _ = Legacy;
+/// ```
+///
+/// as statements
+/// ```
+/// let mut retries = 0;
+/// todo_or_die::remove_code! {
+///     when: after_date(3000, 01, 01);
+///     retries += 1;
+///     retries += 1;
+/// }
+/// assert_eq!(retries, 2);
+/// ```
+///
+/// without any code
+/// ```
+/// todo_or_die::remove_code! { when: after_date(3000, 01, 01); }
+/// ```
+///
+/// with modifiers
+/// ```
+/// todo_or_die::remove_code! {
+///     when: warn: after_date(1990, 01, 01), owner = "@alice";
+///     fn retry_shim() {}
+/// }
+/// ```
+///
+/// ```
+/// todo_or_die::remove_code! {
+///     when: after_date(1990, 01, 01), only(target_os = "no-such-os");
+///     fn retry_shim() {}
+/// }
+/// ```
+///
+/// missing `when`
+/// ```compile_fail
+/// todo_or_die::remove_code! {
+///     after_date(3000, 01, 01);
+///     fn retry_shim() {}
+/// }
+/// ```
+///
+/// missing condition
+/// ```compile_fail
+/// todo_or_die::remove_code! {
+///     fn retry_shim() {}
+/// }
+/// ```
+///
+/// ```compile_fail
+/// todo_or_die::remove_code! {
+///     when: after_date(3000, 13, 01);
+///     fn retry_shim() {}
+/// }
+/// ```
+///
+/// # `keep_until`
+///
+/// ```
+/// todo_or_die::keep_until! {
+///     after_date(1990, 01, 01);
+///     compile_error!("this should be omitted");
+/// }
+/// ```
+///
+/// ```compile_fail
+/// #![deny(warnings)]
+/// todo_or_die::keep_until! {
+///     after_date(1990, 01, 01);
+///     fn fallback() {}
+/// }
+/// ```
+///
+/// ```
+/// todo_or_die::keep_until! {
+///     date(3000, 01, 01);
+///     fn fallback() -> u32 { 1 }
+/// }
+///
+/// assert_eq!(fallback(), 1);
+/// ```
+///
+/// as statements
+/// ```
+/// let mut attempts = 1;
+/// todo_or_die::keep_until! {
+///     date(1990, 01, 01);
+///     attempts += 1;
+/// }
+/// todo_or_die::keep_until! {
+///     date(3000, 01, 01);
+///     attempts += 10;
+/// }
+/// assert_eq!(attempts, 11);
+/// ```
+///
+/// ```compile_fail
+/// todo_or_die::keep_until! {
+///     date(1990, 01, 01), only(unix);
+///     fn fallback() {}
+/// }
+/// ```
+///
+/// ```compile_fail
+/// todo_or_die::keep_until! {
+///     fn fallback() {}
+/// }
+/// ```
+///
+/// # `todo_until`
+///
+/// ```compile_fail
+/// fn paginate() -> u32 {
+///     todo_or_die::todo_until!(date(1990, 01, 01), "implement pagination")
+/// }
+/// ```
+///
+/// ```should_panic
+/// fn paginate() -> u32 {
+///     todo_or_die::todo_until!(date(3000, 01, 01), "implement pagination")
+/// }
+///
+/// paginate();
+/// ```
+///
+/// without a message
+/// ```should_panic
+/// todo_or_die::todo_until!(date(3000, 01, 01));
+/// ```
+///
+/// with format arguments and modifiers
+/// ```should_panic
+/// let page = 2;
+/// todo_or_die::todo_until!(date(3000, 01, 01), "fetch page {}", page, owner = "@alice",);
+/// ```
+///
+/// ```no_run
+/// fn paginate() -> u32 {
+///     todo_or_die::todo_until!(date(1990, 01, 01), "a", only(target_os = "no-such-os"))
+/// }
+///
+/// paginate();
+/// ```
+///
+/// ```compile_fail
+/// todo_or_die::todo_until!("implement pagination");
+/// ```
+///
+/// # `unimplemented_until`
+///
+/// ```compile_fail
+/// trait Storage {
+///     fn compact(&self);
+/// }
+///
+/// struct S3;
+///
+/// impl Storage for S3 {
+///     fn compact(&self) {
+///         todo_or_die::unimplemented_until!(date(1990, 01, 01), "needs compaction upstream")
+///     }
+/// }
+/// ```
+///
+/// ```should_panic
+/// todo_or_die::unimplemented_until!(date(3000, 01, 01), "needs compaction upstream");
+/// ```
+///
+/// any condition
+/// ```compile_fail
+/// todo_or_die::unimplemented_until!(not(date(3000, 01, 01)));
+/// ```
+///
+/// ```should_panic
+/// todo_or_die::unimplemented_until!(any(date(3000, 01, 01), not(date(1990, 01, 01))));
+/// ```
+///
+/// # `stub_fn`
+///
+/// ```compile_fail
+/// fn slow_path(x: u32) -> u32 { x }
+///
+/// todo_or_die::stub_fn! {
+///     when: date(1990, 01, 01);
+///     fn fast_path(x: u32) -> u32 { slow_path(x) }
+/// }
+/// ```
+///
+/// ```
+/// fn slow_path(x: u32) -> u32 { x }
+///
+/// todo_or_die::stub_fn! {
+///     when: date(3000, 01, 01);
+///     /// Uses the slow path until upstream supports the fast one.
+///     #[inline]
+///     pub(crate) fn fast_path<T>(x: T) -> T
+///     where
+///         T: Copy,
+///     {
+///         x
+///     }
+/// }
+///
+/// assert_eq!(fast_path(slow_path(1)), 1);
+/// ```
+///
+/// not a function
+/// ```compile_fail
+/// todo_or_die::stub_fn! {
+///     when: date(3000, 01, 01);
+///     struct Stub {}
+/// }
+/// ```
+///
+/// several functions
+/// ```compile_fail
+/// todo_or_die::stub_fn! {
+///     when: date(3000, 01, 01);
+///     fn a() {}
+///     fn b() {}
+/// }
+/// ```
+///
+/// ```compile_fail
+/// todo_or_die::stub_fn! {
+///     date(3000, 01, 01);
+///     fn a() {}
+/// }
+/// ```
+#[cfg(feature = "time")]
+#[allow(dead_code)]
+fn tests() {}
@@ -0,0 +1,45 @@
+use anyhow::{Context as _, Result};
+use chrono::{DateTime, TimeZone as _, Utc};
+
+/// The current time.
+///
+/// Can be pinned for reproducible builds and tests with `TODO_OR_DIE_NOW`, an RFC 3339
+/// timestamp, or `SOURCE_DATE_EPOCH`, the number of seconds since the unix epoch. If both are
+/// set `TODO_OR_DIE_NOW` wins.
+pub(crate) fn now() -> Result<DateTime<Utc>> {
+    if let Ok(now) = std::env::var("TODO_OR_DIE_NOW") {
+        return DateTime::parse_from_rfc3339(&now)
+            .map(|now| now.with_timezone(&Utc))
+            .with_context(|| format!("Invalid RFC 3339 timestamp `{}` in `TODO_OR_DIE_NOW`", now));
+    }
+
+    if let Ok(epoch) = std::env::var("SOURCE_DATE_EPOCH") {
+        return epoch
+            .parse()
+            .ok()
+            .and_then(|secs| Utc.timestamp_opt(secs, 0).single())
+            .with_context(|| format!("Invalid timestamp `{}` in `SOURCE_DATE_EPOCH`", epoch));
+    }
+
+    Ok(Utc::now())
+}
+
+/// The timezone dates are in, `TODO_OR_DIE_TZ` if it's set and UTC otherwise.
+///
+/// We don't default to local time since that would make the same commit pass on a CI runner
+/// and fail on a developer machine in another timezone.
+#[cfg(any(feature = "time", feature = "endoflife", feature = "tls"))]
+pub(crate) fn timezone() -> Result<chrono_tz::Tz> {
+    match std::env::var("TODO_OR_DIE_TZ") {
+        Ok(name) => name
+            .parse()
+            .map_err(|_| anyhow::anyhow!("Unknown timezone `{}` in `TODO_OR_DIE_TZ`", name)),
+        Err(_) => Ok(chrono_tz::Tz::UTC),
+    }
+}
+
+/// Today's date in [`timezone`].
+#[cfg(feature = "endoflife")]
+pub(crate) fn today() -> Result<chrono::NaiveDate> {
+    Ok(now()?.with_timezone(&timezone()?).date_naive())
+}
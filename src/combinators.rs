@@ -0,0 +1,448 @@
+use crate::Outcome;
+use anyhow::Result;
+use proc_macro2::TokenStream;
+use syn::parse::Parse;
+
+pub(crate) fn all(input: Conditions) -> Result<Option<String>> {
+    let mut messages = Vec::new();
+    let mut passed = false;
+    let mut error = None;
+    for result in input.evaluate_each()? {
+        match result {
+            Ok(Some(msg)) => messages.push(msg),
+            Ok(None) => passed = true,
+            Err(err) => {
+                error.get_or_insert(err);
+            }
+        }
+    }
+
+    if passed {
+        return Ok(None);
+    }
+    if let Some(err) = error {
+        return Err(err);
+    }
+    Ok(Some(messages.join("\n")))
+}
+
+pub(crate) fn any(input: Conditions) -> Result<Option<String>> {
+    let mut message = None;
+    let mut error = None;
+    for result in input.evaluate_each()? {
+        match result {
+            Ok(Some(msg)) => {
+                message.get_or_insert(msg);
+            }
+            Ok(None) => {}
+            Err(err) => {
+                error.get_or_insert(err);
+            }
+        }
+    }
+
+    if message.is_some() {
+        return Ok(message);
+    }
+    if let Some(err) = error {
+        return Err(err);
+    }
+    Ok(None)
+}
+
+pub(crate) fn not(input: Condition) -> Result<Option<String>> {
+    input.evaluate_not()
+}
+
+/// Evaluate the condition an item is waiting on, starting the compile error with a description
+/// of the item.
+pub(crate) fn on_item(condition: Condition, item: &str) -> Result<Option<String>> {
+    Ok(condition
+        .evaluate()?
+        .map(|msg| format!("{}: {}", item, msg)))
+}
+
+/// A check, or a combination of checks, to be evaluated.
+pub(crate) enum Condition {
+    Check {
+        /// The check as written, for error messages.
+        source: String,
+        check: Box<dyn FnOnce() -> Result<Outcome>>,
+    },
+    All(Conditions),
+    Any(Conditions),
+    Not(Box<Condition>),
+}
+
+impl Condition {
+    /// Evaluate the condition, returning the compile error if it holds.
+    pub(crate) fn evaluate(self) -> Result<Option<String>> {
+        match self {
+            Condition::Check { check, .. } => match check()? {
+                Outcome::Die(msg) => Ok(Some(msg)),
+                Outcome::Warn(msg) => {
+                    crate::warn(msg);
+                    Ok(None)
+                }
+                Outcome::Pass => Ok(None),
+            },
+            Condition::All(conditions) => all(conditions),
+            Condition::Any(conditions) => any(conditions),
+            Condition::Not(condition) => condition.evaluate_not(),
+        }
+    }
+
+    fn evaluate_not(self) -> Result<Option<String>> {
+        let source = self.source();
+        match self.evaluate()? {
+            Some(_) => Ok(None),
+            None => Ok(Some(format!(
+                "`{}` doesn't hold. Time to act on this!",
+                source
+            ))),
+        }
+    }
+
+    fn source(&self) -> String {
+        let join = |conditions: &Conditions| {
+            conditions
+                .conditions
+                .iter()
+                .map(Condition::source)
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        match self {
+            Condition::Check { source, .. } => source.clone(),
+            Condition::All(conditions) => format!("all({})", join(conditions)),
+            Condition::Any(conditions) => format!("any({})", join(conditions)),
+            Condition::Not(condition) => format!("not({})", condition.source()),
+        }
+    }
+}
+
+impl Parse for Condition {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let name = input.parse::<syn::Ident>()?;
+        let content;
+        syn::parenthesized!(content in input);
+
+        if name == "all" {
+            Ok(Condition::All(content.parse()?))
+        } else if name == "any" {
+            Ok(Condition::Any(content.parse()?))
+        } else if name == "not" {
+            let condition = content.parse()?;
+            content.parse::<syn::token::Comma>().ok();
+            if !content.is_empty() {
+                return Err(content.error("`not` takes a single condition"));
+            }
+            Ok(Condition::Not(Box::new(condition)))
+        } else {
+            let args = content.parse::<TokenStream>()?;
+            let source = format!("{}({})", name, args);
+            let check = parse_check(&name, args)?;
+            Ok(Condition::Check { source, check })
+        }
+    }
+}
+
+/// One or more comma separated conditions.
+pub(crate) struct Conditions {
+    conditions: Vec<Condition>,
+}
+
+impl Parse for Conditions {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let conditions = input
+            .parse_terminated::<_, syn::token::Comma>(Condition::parse)?
+            .into_iter()
+            .collect::<Vec<_>>();
+
+        if conditions.is_empty() {
+            return Err(input.error("expected at least one condition"));
+        }
+
+        Ok(Self { conditions })
+    }
+}
+
+impl Conditions {
+    /// Evaluate every condition, even if an earlier one fails to evaluate, so a network error only
+    /// fails the combination if the other conditions don't decide the result.
+    ///
+    /// Fatal errors, such as a check being given the wrong input, are returned right away.
+    fn evaluate_each(self) -> Result<Vec<Result<Option<String>>>> {
+        let mut results = Vec::new();
+        for condition in self.conditions {
+            match condition.evaluate() {
+                Err(err) if crate::is_fatal(&err) => return Err(err),
+                result => results.push(result),
+            }
+        }
+        Ok(results)
+    }
+}
+
+/// Parse the arguments of a check and prepare to perform it later.
+#[allow(dead_code)]
+fn prepare<T, R>(
+    args: TokenStream,
+    f: fn(T) -> Result<R>,
+) -> syn::Result<Box<dyn FnOnce() -> Result<Outcome>>>
+where
+    T: Parse + 'static,
+    R: Into<Outcome> + 'static,
+{
+    let input = syn::parse2::<T>(args)?;
+    Ok(Box::new(move || f(input).map(Into::into)))
+}
+
+macro_rules! checks {
+    ( $( $(#[$attr:meta])* $name:ident => $f:path, )* ) => {
+        /// Look up a check by the name of its macro and parse its arguments.
+        #[allow(unused_variables)]
+        fn parse_check(
+            name: &syn::Ident,
+            args: TokenStream,
+        ) -> syn::Result<Box<dyn FnOnce() -> Result<Outcome>>> {
+            $(
+                $(#[$attr])*
+                {
+                    if name == stringify!($name) {
+                        return prepare(args, $f);
+                    }
+                }
+            )*
+
+            Err(syn::Error::new(
+                name.span(),
+                format!("unknown check `{}`, or the feature it requires isn't enabled", name),
+            ))
+        }
+    };
+}
+
+// every macro that performs a check should be listed here, so it can be used with `all!`,
+// `any!`, and `not!`. `check!` doesn't use this, it expands to the macro itself
+checks! {
+    #[cfg(feature = "github")]
+    issue_closed => crate::github::issue_closed,
+    #[cfg(all(feature = "github", feature = "time"))]
+    issue_or_date => crate::github::issue_or_date,
+    #[cfg(feature = "github")]
+    pr_closed => crate::github::pr_closed,
+    #[cfg(feature = "github")]
+    release_asset => crate::github::release_asset,
+    #[cfg(feature = "github")]
+    ghsa_published => crate::github::ghsa_published,
+    #[cfg(feature = "github")]
+    dependabot_merged => crate::github::dependabot_merged,
+    #[cfg(feature = "github")]
+    fork_behind => crate::github::fork_behind,
+    #[cfg(feature = "github")]
+    github_action => crate::github::github_action,
+    #[cfg(feature = "github")]
+    rfc_merged => crate::github::rfc_merged,
+    #[cfg(feature = "time")]
+    after_date => crate::time::after_date,
+    // reads better in attributes, such as `#[deprecate_after(date(2025, 9, 1))]`
+    #[cfg(feature = "time")]
+    date => crate::time::after_date,
+    #[cfg(feature = "time")]
+    before_date => crate::time::before_date,
+    #[cfg(feature = "time")]
+    between_dates => crate::time::between_dates,
+    #[cfg(feature = "time")]
+    after_duration => crate::time::after_duration,
+    #[cfg(feature = "time")]
+    after_business_days => crate::time::after_business_days,
+    #[cfg(feature = "time")]
+    after_quarter => crate::time::after_quarter,
+    #[cfg(feature = "time")]
+    after_week => crate::time::after_week,
+    #[cfg(feature = "time")]
+    after_sprint => crate::time::after_sprint,
+    #[cfg(feature = "time")]
+    dies_in => crate::time::dies_in,
+    #[cfg(feature = "time")]
+    recurring => crate::time::recurring,
+    #[cfg(feature = "time")]
+    after_datetime => crate::time::after_datetime,
+    #[cfg(feature = "crate")]
+    crates_io => crate::krate::crates_io,
+    #[cfg(feature = "crate")]
+    registry => crate::krate::registry,
+    #[cfg(feature = "crate")]
+    crate_yanked => crate::krate::crate_yanked,
+    #[cfg(feature = "crate")]
+    crate_published => crate::krate::crate_published,
+    #[cfg(feature = "crate")]
+    crate_msrv => crate::krate::crate_msrv,
+    #[cfg(feature = "crate")]
+    crate_downloads => crate::krate::crate_downloads,
+    #[cfg(feature = "crate")]
+    crate_release_age => crate::krate::crate_release_age,
+    #[cfg(all(feature = "crate", feature = "github"))]
+    crate_repo_archived => crate::krate::crate_repo_archived,
+    #[cfg(feature = "crate")]
+    crate_exists => crate::krate::crate_exists,
+    #[cfg(feature = "rust")]
+    rust_version => crate::rust::rust_version,
+    #[cfg(feature = "rust")]
+    toolchain_age => crate::rust::toolchain_age,
+    #[cfg(feature = "rust")]
+    clippy_version => crate::rust::clippy_version,
+    #[cfg(feature = "rust")]
+    llvm_version => crate::rust::llvm_version,
+    #[cfg(feature = "rust")]
+    rust_channel => crate::rust::rust_channel,
+    #[cfg(feature = "rust-upstream")]
+    after_rust_release => crate::rust_upstream::after_rust_release,
+    #[cfg(feature = "rust-upstream")]
+    rust_releases_since => crate::rust_upstream::rust_releases_since,
+    #[cfg(feature = "rust-upstream")]
+    msrv_drift => crate::rust_upstream::msrv_drift,
+    #[cfg(feature = "rust-upstream")]
+    feature_stabilized => crate::rust_upstream::feature_stabilized,
+    #[cfg(feature = "rust-upstream")]
+    feature_removed => crate::rust_upstream::feature_removed,
+    #[cfg(feature = "rust-upstream")]
+    cargo_unstable_stabilized => crate::rust_upstream::cargo_unstable_stabilized,
+    #[cfg(feature = "rust-upstream")]
+    target_tier => crate::rust_upstream::target_tier,
+    #[cfg(feature = "rust-upstream")]
+    edition_available => crate::rust_upstream::edition_available,
+    #[cfg(feature = "npm")]
+    npm => crate::npm::npm,
+    #[cfg(feature = "pypi")]
+    pypi => crate::pypi::pypi,
+    #[cfg(feature = "rubygems")]
+    rubygems => crate::rubygems::rubygems,
+    #[cfg(feature = "docker")]
+    docker_tag => crate::docker::docker_tag,
+    #[cfg(feature = "maven")]
+    maven => crate::maven::maven,
+    #[cfg(feature = "nuget")]
+    nuget => crate::nuget::nuget,
+    #[cfg(feature = "hex-pm")]
+    hex_pm => crate::hex_pm::hex_pm,
+    #[cfg(feature = "packagist")]
+    packagist => crate::packagist::packagist,
+    #[cfg(feature = "go")]
+    go_module => crate::go::go_module,
+    #[cfg(feature = "nixpkgs")]
+    nixpkgs => crate::nixpkgs::nixpkgs,
+    #[cfg(feature = "archlinux")]
+    archlinux => crate::archlinux::archlinux,
+    #[cfg(feature = "terraform")]
+    terraform_registry => crate::terraform::terraform_registry,
+    #[cfg(feature = "helm")]
+    helm_chart => crate::helm::helm_chart,
+    #[cfg(feature = "rustsec")]
+    rustsec => crate::rustsec::rustsec,
+    #[cfg(feature = "rustsec")]
+    crate_unmaintained => crate::rustsec::crate_unmaintained,
+    #[cfg(feature = "lockfile")]
+    dependency_version => crate::lockfile::dependency_version,
+    #[cfg(feature = "lockfile")]
+    dependency_gone => crate::lockfile::dependency_gone,
+    #[cfg(feature = "lockfile")]
+    lockfile_age => crate::lockfile::lockfile_age,
+    #[cfg(all(feature = "crate", feature = "lockfile"))]
+    deps_outdated => crate::lockfile::deps_outdated,
+    #[cfg(feature = "git")]
+    git_repo_version => crate::git::git_repo_version,
+    #[cfg(feature = "git")]
+    line_older_than => crate::git::line_older_than,
+    #[cfg(feature = "git")]
+    after_commit => crate::git::after_commit,
+    #[cfg(feature = "tls")]
+    cert_expires => crate::tls::cert_expires,
+    #[cfg(feature = "endoflife")]
+    eol => crate::endoflife::eol,
+    #[cfg(feature = "endoflife")]
+    nodejs_eol => crate::endoflife::nodejs_eol,
+    #[cfg(feature = "endoflife")]
+    kubernetes_eol => crate::endoflife::kubernetes_eol,
+}
+
+/// ```compile_fail
+/// todo_or_die::all!(after_date(1990, 01, 01), after_date(1991, 01, 01));
+/// ```
+///
+/// ```
+/// todo_or_die::all!(after_date(1990, 01, 01), after_date(3000, 01, 01));
+/// ```
+///
+/// ```compile_fail
+/// todo_or_die::any!(after_date(3000, 01, 01), after_date(1990, 01, 01));
+/// ```
+///
+/// ```
+/// todo_or_die::any!(after_date(3000, 01, 01), after_date("3001-01-01", "Europe/Stockholm"),);
+/// ```
+///
+/// ```compile_fail
+/// todo_or_die::not!(after_date(3000, 01, 01));
+/// ```
+///
+/// ```
+/// todo_or_die::not!(after_date(1990, 01, 01));
+/// ```
+///
+/// nested
+/// ```compile_fail
+/// todo_or_die::all!(
+///     after_date(1990, 01, 01),
+///     any(after_date(3000, 01, 01), not(after_date(3001, 01, 01))),
+/// );
+/// ```
+///
+/// ```
+/// todo_or_die::any!(all(after_date(1990, 01, 01), after_date(3000, 01, 01)));
+/// ```
+///
+/// with modifiers
+/// ```
+/// todo_or_die::all!(after_date(1990, 01, 01), only(target_os = "no-such-os"));
+/// ```
+///
+/// ```compile_fail
+/// todo_or_die::any!(after_date(1990, 01, 01), message = "remove the retry shim");
+/// ```
+///
+/// ```compile_fail
+/// todo_or_die::check! { when: not(after_date(3000, 01, 01)), owner: "@alice" }
+/// ```
+///
+/// `date` is short for `after_date`
+/// ```compile_fail
+/// todo_or_die::any!(date(1990, 01, 01));
+/// ```
+///
+/// ```
+/// todo_or_die::any!(date(3000, 01, 01));
+/// ```
+///
+/// unknown check
+/// ```compile_fail
+/// todo_or_die::any!(no_such_check(1));
+/// ```
+///
+/// invalid arguments
+/// ```compile_fail
+/// todo_or_die::any!(after_date(3000, 13, 01));
+/// ```
+///
+/// ```compile_fail
+/// todo_or_die::all!();
+/// ```
+///
+/// ```compile_fail
+/// todo_or_die::not!(after_date(3000, 01, 01), after_date(3000, 01, 01));
+/// ```
+#[cfg(feature = "time")]
+#[allow(dead_code)]
+fn tests() {}
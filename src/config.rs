@@ -0,0 +1,85 @@
+use anyhow::{Context as _, Result};
+use serde::Deserialize;
+use std::{collections::HashMap, fmt, path::Path};
+
+/// Configuration from `[package.metadata.todo-or-die]` in the `Cargo.toml` of the crate being
+/// compiled.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub(crate) struct Config {
+    #[cfg_attr(not(feature = "time"), allow(dead_code))]
+    pub(crate) sprints: Option<Sprints>,
+    /// Checks that have been snoozed, by `id`, and the date they're snoozed until.
+    pub(crate) snooze: HashMap<String, String>,
+}
+
+/// The sprint cadence, from `[package.metadata.todo-or-die.sprints]`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(not(feature = "time"), allow(dead_code))]
+pub(crate) struct Sprints {
+    /// The first day of the first sprint, such as `"2025-01-06"`.
+    pub(crate) start: String,
+    pub(crate) length_days: u64,
+    /// The number of the first sprint.
+    #[serde(default = "default_first_sprint")]
+    pub(crate) first: u64,
+}
+
+fn default_first_sprint() -> u64 {
+    1
+}
+
+impl Config {
+    pub(crate) fn read() -> Result<Self> {
+        #[derive(Deserialize)]
+        struct Manifest {
+            #[serde(default)]
+            package: Package,
+        }
+
+        #[derive(Default, Deserialize)]
+        struct Package {
+            #[serde(default)]
+            metadata: Metadata,
+        }
+
+        #[derive(Default, Deserialize)]
+        struct Metadata {
+            #[serde(default, rename = "todo-or-die")]
+            todo_or_die: Config,
+        }
+
+        let manifest_dir =
+            std::env::var("CARGO_MANIFEST_DIR").context("`CARGO_MANIFEST_DIR` isn't set")?;
+        let path = Path::new(&manifest_dir).join("Cargo.toml");
+
+        let contents = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+
+        let manifest = toml::from_str::<Manifest>(&contents).map_err(|err| {
+            ConfigError(format!(
+                "Invalid `[package.metadata.todo-or-die]` in {}: {}",
+                path.display(),
+                err
+            ))
+        })?;
+
+        Ok(manifest.package.metadata.todo_or_die)
+    }
+}
+
+/// The configuration is missing or invalid.
+///
+/// Unlike network errors this is always reported as a compile error since the check can never
+/// succeed.
+#[derive(Debug)]
+pub(crate) struct ConfigError(pub(crate) String);
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl std::error::Error for ConfigError {}
@@ -0,0 +1,38 @@
+use crate::modifiers::Modifiers;
+use proc_macro::{Diagnostic, Level, Span};
+
+/// Emit the message of a failed check as a compile error at `span`.
+///
+/// The first paragraph of `msg` is the diagnostic itself and any further paragraphs, such as the
+/// URL of a closed issue, become help. `message`, `owner`, and `link` are added as help and notes
+/// rather than being appended to the message.
+pub(crate) fn emit(msg: &str, modifiers: &Modifiers, span: Span) {
+    let mut paragraphs = msg.split("\n\n");
+    let mut first = paragraphs.next().unwrap_or_default().to_owned();
+    if let Some(id) = &modifiers.id {
+        first = format!("[{}] {}", id, first);
+    }
+
+    let mut diagnostic = Diagnostic::spanned(span, Level::Error, first);
+    for paragraph in paragraphs {
+        diagnostic = diagnostic.help(paragraph);
+    }
+    if let Some(message) = &modifiers.message {
+        diagnostic = diagnostic.help(message.as_str());
+    }
+    if let Some(owner) = &modifiers.owner {
+        diagnostic = diagnostic.note(format!("owner: {}", owner));
+    }
+    if let Some(link) = &modifiers.link {
+        diagnostic = diagnostic.note(format!("see {}", link));
+    }
+    diagnostic.emit();
+}
+
+/// Emit a warning for an error that didn't prevent compiling, rather than printing it to stderr
+/// where it's easily missed.
+pub(crate) fn emit_error(err: &anyhow::Error) {
+    Diagnostic::spanned(Span::call_site(), Level::Warning, "something went wrong")
+        .note(format!("{:#}", err))
+        .emit();
+}
@@ -0,0 +1,111 @@
+use crate::http::{request, request_optional};
+use anyhow::Result;
+use hyper::Request;
+use serde::Deserialize;
+use syn::parse::Parse;
+
+pub(crate) fn docker_tag(input: Input) -> Result<Option<String>> {
+    #[derive(Debug, Deserialize)]
+    struct Repository {}
+
+    #[derive(Debug, Deserialize)]
+    struct Tag {
+        digest: Option<String>,
+    }
+
+    // make sure the repository exists so typos aren't mistaken for the tag not existing yet
+    request::<Repository>(
+        Request::builder()
+            .uri(format!(
+                "https://hub.docker.com/v2/repositories/{}",
+                input.repository
+            ))
+            .body(())
+            .unwrap(),
+    )?;
+
+    let tag = request_optional::<Tag>(
+        Request::builder()
+            .uri(format!(
+                "https://hub.docker.com/v2/repositories/{}/tags/{}",
+                input.repository, input.tag
+            ))
+            .body(())
+            .unwrap(),
+    )?;
+
+    let tag = match tag {
+        Some(tag) => tag,
+        None => return Ok(None),
+    };
+
+    match (&input.digest, &tag.digest) {
+        (None, _) => Ok(Some(format!(
+            "{}:{} exists. Time to act on this!",
+            input.repository, input.tag
+        ))),
+        (Some(expected), Some(actual)) if expected != actual => Ok(Some(format!(
+            "The digest of {}:{} has changed to {}. Time to act on this!",
+            input.repository, input.tag, actual
+        ))),
+        (Some(_), _) => Ok(None),
+    }
+}
+
+pub(crate) struct Input {
+    repository: String,
+    tag: String,
+    digest: Option<String>,
+}
+
+impl Parse for Input {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let repository = input.parse::<syn::LitStr>()?.value();
+        let repository = if repository.contains('/') {
+            repository
+        } else {
+            format!("library/{}", repository)
+        };
+
+        input.parse::<syn::token::Comma>()?;
+
+        let tag = input.parse::<syn::LitStr>()?.value();
+
+        let mut digest = None;
+        if input.parse::<syn::token::Comma>().is_ok() && !input.is_empty() {
+            let ident = input.parse::<syn::Ident>()?;
+            if ident != "digest" {
+                return Err(syn::Error::new(ident.span(), "expected `digest`"));
+            }
+            input.parse::<syn::token::Eq>()?;
+            digest = Some(input.parse::<syn::LitStr>()?.value());
+
+            input.parse::<syn::token::Comma>().ok();
+        }
+
+        Ok(Self {
+            repository,
+            tag,
+            digest,
+        })
+    }
+}
+
+/// ```compile_fail
+/// todo_or_die::docker_tag!("library/debian", "bookworm");
+/// ```
+///
+/// ```
+/// todo_or_die::docker_tag!("library/debian", "this-tag-does-not-exist");
+/// ```
+///
+/// digest changed
+/// ```compile_fail
+/// todo_or_die::docker_tag!(
+///     "debian",
+///     "bookworm",
+///     digest = "sha256:0000000000000000000000000000000000000000000000000000000000000000",
+/// );
+/// ```
+#[allow(dead_code)]
+fn tests() {}
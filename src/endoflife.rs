@@ -0,0 +1,243 @@
+use crate::http::request;
+use anyhow::Result;
+use chrono::NaiveDate;
+use hyper::Request;
+use serde::Deserialize;
+use syn::parse::Parse;
+
+pub(crate) fn eol(input: Input) -> Result<Option<String>> {
+    let eol = eol_date(&input.product, &input.cycle)?;
+    check_eol(
+        &format!("{} {}", input.product, input.cycle),
+        eol,
+        input.days_before,
+    )
+}
+
+pub(crate) fn nodejs_eol(input: NodeInput) -> Result<Option<String>> {
+    let eol = eol_date("nodejs", &input.major)?;
+    check_eol(&format!("Node.js {}", input.major), eol, input.days_before)
+}
+
+pub(crate) fn kubernetes_eol(input: KubernetesInput) -> Result<Option<String>> {
+    let eol = eol_date("kubernetes", &input.minor)?;
+    check_eol(
+        &format!("Kubernetes {}", input.minor),
+        eol,
+        input.days_before,
+    )
+}
+
+/// When a release cycle of a product on endoflife.date reaches end-of-life.
+pub(crate) fn eol_date(product: &str, cycle: &str) -> Result<Eol> {
+    #[derive(Deserialize)]
+    struct Cycle {
+        eol: Eol,
+    }
+
+    let cycle = request::<Cycle>(
+        Request::builder()
+            .uri(format!(
+                "https://endoflife.date/api/{}/{}.json",
+                product, cycle
+            ))
+            .body(())
+            .unwrap(),
+    )?;
+
+    Ok(cycle.eol)
+}
+
+/// The `eol` field, which is either a date or whether it has been reached.
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub(crate) enum Eol {
+    Date(NaiveDate),
+    Reached(bool),
+}
+
+/// Produce an error message if `eol` is in the past or within `days_before` days.
+pub(crate) fn check_eol(name: &str, eol: Eol, days_before: u32) -> Result<Option<String>> {
+    let date = match eol {
+        Eol::Date(date) => date,
+        Eol::Reached(true) => {
+            return Ok(Some(format!(
+                "{} has reached end-of-life. Time to act on this!",
+                name
+            )))
+        }
+        Eol::Reached(false) => return Ok(None),
+    };
+
+    let today = crate::clock::today()?;
+    let days_left = date.signed_duration_since(today).num_days();
+
+    if days_left <= 0 {
+        Ok(Some(format!(
+            "{} reached end-of-life on {}. Time to act on this!",
+            name, date
+        )))
+    } else if days_left <= days_before.into() {
+        Ok(Some(format!(
+            "{} reaches end-of-life on {}, in {} days. Time to act on this!",
+            name, date, days_left
+        )))
+    } else {
+        Ok(None)
+    }
+}
+
+pub(crate) struct Input {
+    product: String,
+    cycle: String,
+    days_before: u32,
+}
+
+impl Parse for Input {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let product = input.parse::<syn::LitStr>()?.value();
+        input.parse::<syn::token::Comma>()?;
+        let cycle = input.parse::<syn::LitStr>()?.value();
+        let days_before = parse_days_before(input)?;
+
+        Ok(Self {
+            product,
+            cycle,
+            days_before,
+        })
+    }
+}
+
+pub(crate) struct NodeInput {
+    major: String,
+    days_before: u32,
+}
+
+impl Parse for NodeInput {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let lit = input.parse::<syn::LitStr>()?;
+        let major = lit.value();
+        if !is_number(&major) {
+            return Err(syn::Error::new(
+                lit.span(),
+                "expected a major version, such as `\"20\"`",
+            ));
+        }
+        let days_before = parse_days_before(input)?;
+
+        Ok(Self { major, days_before })
+    }
+}
+
+pub(crate) struct KubernetesInput {
+    minor: String,
+    days_before: u32,
+}
+
+impl Parse for KubernetesInput {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let lit = input.parse::<syn::LitStr>()?;
+        let minor = lit.value();
+        let is_minor = matches!(
+            minor.split_once('.'),
+            Some((major, minor)) if is_number(major) && is_number(minor)
+        );
+        if !is_minor {
+            return Err(syn::Error::new(
+                lit.span(),
+                "expected a minor version, such as `\"1.29\"`",
+            ));
+        }
+        let days_before = parse_days_before(input)?;
+
+        Ok(Self { minor, days_before })
+    }
+}
+
+fn is_number(s: &str) -> bool {
+    !s.is_empty() && s.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Parse an optional trailing `days_before = N`, and trailing comma.
+pub(crate) fn parse_days_before(input: syn::parse::ParseStream) -> syn::Result<u32> {
+    let mut days_before = 0;
+    if input.parse::<syn::token::Comma>().is_ok() && !input.is_empty() {
+        let ident = input.parse::<syn::Ident>()?;
+        if ident != "days_before" {
+            return Err(syn::Error::new(ident.span(), "expected `days_before`"));
+        }
+        input.parse::<syn::token::Eq>()?;
+        days_before = input.parse::<syn::LitInt>()?.base10_parse()?;
+
+        input.parse::<syn::token::Comma>().ok();
+    }
+    Ok(days_before)
+}
+
+/// ```compile_fail
+/// todo_or_die::eol!("ubuntu", "14.04");
+/// ```
+///
+/// ```
+/// todo_or_die::eol!("ubuntu", "24.04");
+/// ```
+///
+/// ```compile_fail
+/// todo_or_die::eol!("ubuntu", "24.04", days_before = 100000);
+/// ```
+///
+/// unknown product
+/// ```compile_fail
+/// todo_or_die::eol!("this-product-does-not-exist", "1.0");
+/// ```
+///
+/// unknown cycle
+/// ```compile_fail
+/// todo_or_die::eol!("ubuntu", "1.0");
+/// ```
+///
+/// ```compile_fail
+/// todo_or_die::eol!("ubuntu", "24.04", days = 30);
+/// ```
+///
+/// # `nodejs_eol`
+///
+/// ```compile_fail
+/// todo_or_die::nodejs_eol!("16");
+/// ```
+///
+/// ```
+/// todo_or_die::nodejs_eol!("24", days_before = 60);
+/// ```
+///
+/// ```compile_fail
+/// todo_or_die::nodejs_eol!("24", days_before = 100000);
+/// ```
+///
+/// ```compile_fail
+/// todo_or_die::nodejs_eol!("v20");
+/// ```
+///
+/// # `kubernetes_eol`
+///
+/// ```compile_fail
+/// todo_or_die::kubernetes_eol!("1.20");
+/// ```
+///
+/// ```
+/// todo_or_die::kubernetes_eol!("1.34", days_before = 30);
+/// ```
+///
+/// ```compile_fail
+/// todo_or_die::kubernetes_eol!("1.34", days_before = 100000);
+/// ```
+///
+/// ```compile_fail
+/// todo_or_die::kubernetes_eol!("1");
+/// ```
+///
+/// ```compile_fail
+/// todo_or_die::kubernetes_eol!("1.29.1");
+/// ```
+#[allow(dead_code)]
+fn tests() {}
@@ -0,0 +1,339 @@
+use crate::package;
+use anyhow::{Context as _, Result};
+use chrono::{DateTime, TimeZone as _, Utc};
+use std::{
+    io::Read,
+    path::Path,
+    process::{Command, Stdio},
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
+use syn::parse::Parse;
+
+pub(crate) fn git_repo_version(input: package::Input) -> Result<Option<String>> {
+    let tags = ls_remote_tags(&input.name)?;
+
+    let latest_version = package::latest_stable(&tags)
+        .with_context(|| format!("No version tags found for {}", input.name))?;
+
+    Ok(package::check(&input, "git", &latest_version))
+}
+
+/// List the tags of a remote repository with `git ls-remote`.
+///
+/// This works for any git host and, unlike the GitHub API, isn't rate limited.
+fn ls_remote_tags(url: &str) -> Result<Vec<String>> {
+    const TIMEOUT: Duration = Duration::from_secs(10);
+
+    // local repositories can still be read
+    if !Path::new(url).exists() {
+        crate::http::ensure_online()?;
+    }
+
+    let mut child = Command::new("git")
+        .arg("ls-remote")
+        .arg("--tags")
+        .arg("--")
+        .arg(url)
+        // fail rather than waiting for credentials
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("Failed to run `git ls-remote`")?;
+
+    // read the output while waiting, otherwise git blocks once the pipe buffers are full
+    let stdout = read_in_background(child.stdout.take());
+    let stderr = read_in_background(child.stderr.take());
+
+    let started_at = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if started_at.elapsed() > TIMEOUT {
+            child.kill().ok();
+            anyhow::bail!("`git ls-remote` timed out");
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    };
+
+    if !status.success() {
+        let stderr = stderr.join().unwrap_or_default();
+        anyhow::bail!(
+            "`git ls-remote` failed: {}",
+            String::from_utf8_lossy(&stderr).trim()
+        );
+    }
+
+    let stdout = stdout.join().unwrap_or_default();
+    let stdout = String::from_utf8(stdout).context("Output wasn't valid UTF-8")?;
+
+    Ok(stdout
+        .lines()
+        .filter_map(|line| line.split('\t').nth(1))
+        .filter_map(|reference| reference.strip_prefix("refs/tags/"))
+        // annotated tags are listed twice, once with `^{}` for the commit they point to
+        .filter(|tag| !tag.ends_with("^{}"))
+        .map(|tag| tag.to_owned())
+        .collect())
+}
+
+/// Read all of `pipe` on another thread.
+fn read_in_background<R>(pipe: Option<R>) -> JoinHandle<Vec<u8>>
+where
+    R: Read + Send + 'static,
+{
+    std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(mut pipe) = pipe {
+            pipe.read_to_end(&mut buf).ok();
+        }
+        buf
+    })
+}
+
+pub(crate) fn line_older_than(input: LineAge) -> Result<Option<String>> {
+    let span = proc_macro::Span::call_site();
+    let path = span
+        .local_file()
+        .context("Couldn't determine which file the macro was called from")?;
+    let path = std::fs::canonicalize(&path)
+        .with_context(|| format!("Failed to resolve {}", path.display()))?;
+    let line = span.line();
+
+    let output = Command::new("git")
+        .arg("blame")
+        .arg("--porcelain")
+        .arg("-L")
+        .arg(format!("{},{}", line, line))
+        .arg("--")
+        .arg(path.file_name().context("Invalid path")?)
+        .current_dir(path.parent().context("Invalid path")?)
+        .output()
+        .context("Failed to run `git blame`")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "`git blame` failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    // lines that haven't been committed yet are attributed to the all zeros commit
+    let stdout = String::from_utf8(output.stdout).context("Output wasn't valid UTF-8")?;
+    if stdout.starts_with("0000000000000000000000000000000000000000 ") {
+        return Ok(None);
+    }
+
+    let authored_at = stdout
+        .lines()
+        .find_map(|line| line.strip_prefix("author-time "))
+        .and_then(|secs| secs.trim().parse().ok())
+        .and_then(|secs| Utc.timestamp_opt(secs, 0).single())
+        .context("Couldn't find the author time in the output of `git blame`")?;
+
+    let days = (crate::clock::now()? - authored_at).num_days();
+
+    if days >= 0 && days as u64 >= input.days {
+        Ok(Some(format!(
+            "This line was written {} days ago, on {}. Time to act on this!",
+            days,
+            authored_at.date_naive()
+        )))
+    } else {
+        Ok(None)
+    }
+}
+
+pub(crate) fn after_commit(input: AfterCommit) -> Result<Option<String>> {
+    let committed_at = match local_commit_date(&input.sha)? {
+        Some(committed_at) => committed_at,
+        None => match &input.repo {
+            #[cfg(feature = "github")]
+            Some((org, repo)) => crate::github::commit_date(org, repo, &input.sha)?,
+            _ => anyhow::bail!("Commit `{}` not found in the local repository", input.sha),
+        },
+    };
+
+    let deadline = chrono::Duration::try_days(input.days)
+        .and_then(|days| committed_at.checked_add_signed(days))
+        .ok_or_else(|| syn::Error::new(input.days_span, "`days` is out of range"))?;
+
+    if deadline <= crate::clock::now()? {
+        Ok(Some(format!(
+            "Commit {} landed on {}, more than {} days ago. Time to act on this!",
+            input.sha,
+            committed_at.date_naive(),
+            input.days
+        )))
+    } else {
+        Ok(None)
+    }
+}
+
+/// When a commit was committed, if it exists in the repository the crate is in.
+fn local_commit_date(sha: &str) -> Result<Option<DateTime<Utc>>> {
+    let dir = std::env::var("CARGO_MANIFEST_DIR").context("`CARGO_MANIFEST_DIR` isn't set")?;
+
+    let output = Command::new("git")
+        .arg("show")
+        .arg("-s")
+        .arg("--format=%ct")
+        .arg(format!("{}^{{commit}}", sha))
+        .arg("--")
+        .current_dir(dir)
+        .output()
+        .context("Failed to run `git show`")?;
+    if !output.status.success() {
+        return Ok(None);
+    }
+
+    let secs = String::from_utf8(output.stdout)
+        .context("Output wasn't valid UTF-8")?
+        .trim()
+        .parse()
+        .context("Couldn't parse the output of `git show`")?;
+
+    Ok(Utc.timestamp_opt(secs, 0).single())
+}
+
+pub(crate) struct AfterCommit {
+    sha: String,
+    days: i64,
+    days_span: proc_macro2::Span,
+    repo: Option<(String, String)>,
+}
+
+impl Parse for AfterCommit {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut sha = None;
+        let mut days = None;
+        let mut repo = None;
+
+        while !input.is_empty() {
+            let ident = input.parse::<syn::Ident>()?;
+            input.parse::<syn::token::Eq>()?;
+
+            if ident == "sha" {
+                let lit = input.parse::<syn::LitStr>()?;
+                let value = lit.value();
+                if !(4..=40).contains(&value.len()) || !value.chars().all(|c| c.is_ascii_hexdigit())
+                {
+                    return Err(syn::Error::new(lit.span(), "expected a commit sha"));
+                }
+                sha = Some(value);
+            } else if ident == "days" {
+                let lit = input.parse::<syn::LitInt>()?;
+                days = Some((lit.base10_parse()?, lit.span()));
+            } else if ident == "repo" {
+                let lit = input.parse::<syn::LitStr>()?;
+                if cfg!(not(feature = "github")) {
+                    return Err(syn::Error::new(
+                        lit.span(),
+                        "looking up commits on GitHub requires the `github` feature",
+                    ));
+                }
+                let value = lit.value();
+                let mut parts = value.splitn(2, '/');
+                match (parts.next(), parts.next()) {
+                    (Some(org), Some(name)) if !org.is_empty() && !name.is_empty() => {
+                        repo = Some((org.to_owned(), name.to_owned()));
+                    }
+                    _ => return Err(syn::Error::new(lit.span(), "expected `org/repo`")),
+                }
+            } else {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    "expected `sha`, `days`, or `repo`",
+                ));
+            }
+
+            if input.parse::<syn::token::Comma>().is_err() {
+                break;
+            }
+        }
+
+        let missing = |name| syn::Error::new(input.span(), format!("missing `{}`", name));
+        let (days, days_span) = days.ok_or_else(|| missing("days"))?;
+        Ok(Self {
+            sha: sha.ok_or_else(|| missing("sha"))?,
+            days,
+            days_span,
+            repo,
+        })
+    }
+}
+
+pub(crate) struct LineAge {
+    days: u64,
+}
+
+impl Parse for LineAge {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let ident = input.parse::<syn::Ident>()?;
+        if ident != "days" {
+            return Err(syn::Error::new(ident.span(), "expected `days`"));
+        }
+        input.parse::<syn::token::Eq>()?;
+        let days = input.parse::<syn::LitInt>()?.base10_parse()?;
+
+        input.parse::<syn::token::Comma>().ok();
+
+        Ok(Self { days })
+    }
+}
+
+/// ```compile_fail
+/// todo_or_die::git_repo_version!("https://github.com/tokio-rs/axum", ">=0.5");
+/// ```
+///
+/// ```
+/// todo_or_die::git_repo_version!("https://github.com/tokio-rs/axum", ">=1000");
+/// ```
+///
+/// # `line_older_than`
+///
+/// ```
+/// todo_or_die::line_older_than!(days = 100000);
+/// ```
+///
+/// ```compile_fail
+/// todo_or_die::line_older_than!(months = 6);
+/// ```
+///
+/// # `after_commit`
+///
+/// the first commit of rust-lang/rust
+/// ```compile_fail
+/// todo_or_die::after_commit!(
+///     sha = "c01efc669f09508b55eced32d3c88702578a7c3e",
+///     days = 30,
+///     repo = "rust-lang/rust",
+/// );
+/// ```
+///
+/// ```
+/// todo_or_die::after_commit!(
+///     sha = "c01efc669f09508b55eced32d3c88702578a7c3e",
+///     days = 1000000,
+///     repo = "rust-lang/rust",
+/// );
+/// ```
+///
+/// unknown commit without `repo`
+/// ```
+/// todo_or_die::after_commit!(sha = "0000000", days = 0);
+/// ```
+///
+/// invalid sha
+/// ```compile_fail
+/// todo_or_die::after_commit!(sha = "main", days = 30);
+/// ```
+///
+/// missing `days`
+/// ```compile_fail
+/// todo_or_die::after_commit!(sha = "c4ca4f1");
+/// ```
+#[allow(dead_code)]
+fn tests() {}
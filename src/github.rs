@@ -1,40 +1,70 @@
-use crate::http::request;
+use crate::{
+    http::{request, request_following_redirects, NotFound, NotFoundIsntFatal},
+    package,
+    threshold::Threshold,
+};
 use anyhow::{Context as _, Result};
+use chrono::{DateTime, NaiveDate, Utc};
 use hyper::{
     header::HeaderValue,
     header::{ACCEPT, AUTHORIZATION},
     Request,
 };
-use serde::Deserialize;
+use semver::{Version, VersionReq};
+use serde::{de::DeserializeOwned, Deserialize};
+use std::fmt;
 use syn::parse::Parse;
 
-pub(crate) fn issue_closed(input: OrgRepoIssue) -> Result<Option<String>> {
+pub(crate) fn issue_closed(input: Issues) -> Result<Option<String>> {
     #[derive(Deserialize, Debug)]
     struct Issue {
         closed_at: Option<String>,
+        html_url: String,
     }
 
-    let OrgRepoIssue {
-        org,
-        repo,
-        issue: issue_number,
-    } = input;
+    let Issues { mode, issues } = input;
 
-    let issue = request::<Issue>(github_request(
-        Request::builder()
-            .uri(format!(
-                "https://api.github.com/repos/{}/{}/issues/{}",
-                org, repo, issue_number
-            ))
-            .body(())
-            .unwrap(),
-    )?)?;
+    let mut closed = Vec::new();
+    let mut any_open = false;
+    for mut issue in issues {
+        let OrgRepoIssue {
+            org_repo,
+            issue: issue_number,
+        } = &mut issue;
 
-    if issue.closed_at.is_some() {
-        Ok(Some(format!(
-            "{}/{}#{} is closed. Time to act on this!",
-            org, repo, issue_number
-        )))
+        let found = org_repo.get::<Issue>(&format!("/issues/{}", issue_number))?;
+        if found.closed_at.is_some() {
+            closed.push((issue, found.html_url));
+        } else {
+            any_open = true;
+        }
+    }
+
+    let triggered = match mode {
+        Mode::Any => !closed.is_empty(),
+        Mode::All => !any_open,
+    };
+
+    if triggered {
+        let names = closed
+            .iter()
+            .map(|(issue, _)| format!("{}#{}", issue.org_repo, issue.issue))
+            .collect::<Vec<_>>();
+
+        let mut msg = if let [name] = &*names {
+            format!("{} is closed. Time to act on this!", name)
+        } else {
+            format!("{} are closed. Time to act on this!", names.join(", "))
+        };
+        for (_, url) in &closed {
+            msg = format!("{}\n\nSee {}", msg, url);
+        }
+
+        Ok(Some(
+            closed
+                .iter()
+                .fold(msg, |msg, (issue, _)| issue.org_repo.message(msg)),
+        ))
     } else {
         Ok(None)
     }
@@ -43,35 +73,374 @@ pub(crate) fn issue_closed(input: OrgRepoIssue) -> Result<Option<String>> {
 #[derive(Deserialize, Debug)]
 struct PullRequest {
     state: String,
+    html_url: String,
+}
+
+#[cfg(feature = "time")]
+pub(crate) fn issue_or_date(input: IssueOrDate) -> Result<crate::Outcome> {
+    // the date doesn't require network requests so check that first
+    let outcome = crate::time::after_date(input.date)?;
+    if let crate::Outcome::Die(_) = outcome {
+        return Ok(outcome);
+    }
+
+    let closed = issue_closed(Issues {
+        mode: Mode::Any,
+        issues: vec![input.issue],
+    })?;
+
+    Ok(match closed {
+        Some(msg) => crate::Outcome::Die(msg),
+        None => outcome,
+    })
 }
 
-pub(crate) fn pr_closed(input: OrgRepoIssue) -> Result<Option<String>> {
-    let OrgRepoIssue {
-        org,
-        repo,
+pub(crate) fn pr_closed(input: OrgRepoPullRequest) -> Result<Option<String>> {
+    let OrgRepoPullRequest(OrgRepoIssue {
+        mut org_repo,
         issue: issue_number,
+    }) = input;
+
+    let pr = org_repo.get::<PullRequest>(&format!("/pulls/{}", issue_number))?;
+
+    if pr.state == "closed" {
+        Ok(Some(org_repo.message(format!(
+            "{}#{} is closed. Time to act on this!\n\nSee {}",
+            org_repo, issue_number, pr.html_url
+        ))))
+    } else {
+        Ok(None)
+    }
+}
+
+pub(crate) fn release_asset(input: ReleaseAsset) -> Result<Option<String>> {
+    #[derive(Deserialize, Debug)]
+    struct Release {
+        assets: Vec<Asset>,
+    }
+
+    #[derive(Deserialize, Debug)]
+    struct Asset {
+        name: String,
+    }
+
+    let ReleaseAsset {
+        mut org_repo,
+        tag,
+        pattern,
     } = input;
 
-    let pr = request::<PullRequest>(github_request(
+    let release = org_repo.get::<Release>(&format!(
+        "/releases/tags/{}",
+        crate::http::percent_encode(&tag)
+    ))?;
+
+    if let Some(asset) = release
+        .assets
+        .iter()
+        .find(|asset| glob_matches(&pattern, &asset.name))
+    {
+        Ok(Some(org_repo.message(format!(
+            "Release {} of {} has an asset matching `{}` ({}). Time to act on this!",
+            tag, org_repo, pattern, asset.name
+        ))))
+    } else {
+        Ok(None)
+    }
+}
+
+pub(crate) fn ghsa_published(input: GhsaPublished) -> Result<Option<String>> {
+    #[derive(Deserialize, Debug)]
+    struct Advisory {
+        ghsa_id: String,
+        severity: Option<Severity>,
+        published_at: Option<DateTime<Utc>>,
+    }
+
+    let GhsaPublished {
+        mut org_repo,
+        since,
+        severity,
+    } = input;
+
+    let advisories =
+        org_repo.get::<Vec<Advisory>>("/security-advisories?state=published&per_page=100")?;
+
+    let advisory = advisories.into_iter().find(|advisory| {
+        let published_at = match advisory.published_at {
+            Some(published_at) => published_at,
+            None => return false,
+        };
+        if advisory.severity == Some(Severity::Unknown) {
+            return false;
+        }
+
+        let after_since = since.is_none_or(|since| published_at.date_naive() >= since);
+        let severe_enough = severity.is_none_or(|severity| {
+            advisory
+                .severity
+                .is_some_and(|advisory| advisory >= severity)
+        });
+
+        after_since && severe_enough
+    });
+
+    if let Some(advisory) = advisory {
+        Ok(Some(org_repo.message(format!(
+            "Security advisory {} has been published for {}. Time to act on this!",
+            advisory.ghsa_id, org_repo
+        ))))
+    } else {
+        Ok(None)
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+enum Severity {
+    Low,
+    Medium,
+    High,
+    Critical,
+    /// A severity added after this was written, which we can't compare against.
+    #[serde(other)]
+    Unknown,
+}
+
+pub(crate) fn dependabot_merged(input: DependabotMerged) -> Result<Option<String>> {
+    #[derive(Deserialize, Debug)]
+    struct SearchResults {
+        items: Vec<PullRequestItem>,
+    }
+
+    #[derive(Deserialize, Debug)]
+    struct PullRequestItem {
+        number: u64,
+        title: String,
+    }
+
+    let DependabotMerged {
+        mut org_repo,
+        dependency,
+        version_req,
+    } = input;
+
+    // the search API isn't scoped to the repository so look it up first, to follow it if it has
+    // moved
+    org_repo.get::<serde::de::IgnoredAny>("")?;
+
+    let query = format!(
+        "repo:{} is:pr is:merged author:app/dependabot \"{}\"",
+        org_repo, dependency
+    );
+    let results = request::<SearchResults>(github_request(
         Request::builder()
             .uri(format!(
-                "https://api.github.com/repos/{}/{}/pulls/{}",
-                org, repo, issue_number
+                "https://api.github.com/search/issues?q={}&per_page=100",
+                crate::http::percent_encode(&query)
             ))
             .body(())
             .unwrap(),
     )?)?;
 
-    if pr.state == "closed" {
+    let merged = results.items.into_iter().find_map(|pr| {
+        let version = bumped_to_version(&pr.title, &dependency)?;
+        if version_req.matches(&version) {
+            Some((pr.number, version))
+        } else {
+            None
+        }
+    });
+
+    if let Some((number, version)) = merged {
+        Ok(Some(org_repo.message(format!(
+            "{}#{} bumping {} to {} has been merged. Time to act on this!",
+            org_repo, number, dependency, version
+        ))))
+    } else {
+        Ok(None)
+    }
+}
+
+pub(crate) fn fork_behind(input: ForkBehind) -> Result<Option<String>> {
+    #[derive(Deserialize, Debug)]
+    struct Repository {
+        default_branch: String,
+    }
+
+    #[derive(Deserialize, Debug)]
+    struct Comparison {
+        behind_by: u64,
+    }
+
+    let ForkBehind {
+        mut fork,
+        mut upstream,
+        threshold,
+    } = input;
+
+    let fork_branch = fork.get::<Repository>("")?.default_branch;
+    let upstream_branch = upstream.get::<Repository>("")?.default_branch;
+
+    // the fork might not have the same name as upstream so include both the owner and the repo
+    let comparison = upstream.get::<Comparison>(&format!(
+        "/compare/{}...{}:{}:{}",
+        upstream_branch, fork.org, fork.repo, fork_branch
+    ))?;
+
+    if threshold.matches(comparison.behind_by) {
+        Ok(Some(upstream.message(fork.message(format!(
+            "{} is {} commits behind {}. Time to act on this!",
+            fork, comparison.behind_by, upstream
+        )))))
+    } else {
+        Ok(None)
+    }
+}
+
+pub(crate) fn github_action(input: GithubAction) -> Result<Option<String>> {
+    #[derive(Deserialize, Debug)]
+    struct Tag {
+        name: String,
+    }
+
+    let GithubAction {
+        mut org_repo,
+        version_req,
+    } = input;
+
+    let tags = org_repo.get::<Vec<Tag>>("/tags?per_page=100")?;
+
+    let latest_version = package::latest_stable(tags.iter().map(|tag| &tag.name))
+        .with_context(|| format!("No version tags found for {}", org_repo))?;
+
+    let package = package::Input {
+        name: org_repo.to_string(),
+        version_req,
+    };
+
+    Ok(package::check(&package, "GitHub", &latest_version).map(|msg| org_repo.message(msg)))
+}
+
+pub(crate) fn rfc_merged(input: Rfc) -> Result<Option<String>> {
+    #[derive(Deserialize, Debug)]
+    struct PullRequest {
+        title: String,
+        merged_at: Option<DateTime<Utc>>,
+    }
+
+    let mut org_repo = OrgRepo {
+        org: "rust-lang".to_owned(),
+        repo: "rfcs".to_owned(),
+        moved_from: None,
+    };
+
+    let pr = org_repo.get::<PullRequest>(&format!("/pulls/{}", input.number))?;
+
+    if let Some(merged_at) = pr.merged_at {
         Ok(Some(format!(
-            "{}/{}#{} is closed. Time to act on this!",
-            org, repo, issue_number
+            "RFC {} ({}) was merged on {}: https://github.com/rust-lang/rfcs/pull/{}. Time to act on this!",
+            input.number,
+            pr.title,
+            merged_at.date_naive(),
+            input.number
         )))
     } else {
         Ok(None)
     }
 }
 
+/// Whether a repository has been archived, for checks that combine GitHub with other sources.
+#[cfg(feature = "crate")]
+pub(crate) fn repo_archived(org: &str, repo: &str) -> Result<bool> {
+    #[derive(Deserialize, Debug)]
+    struct Repository {
+        archived: bool,
+    }
+
+    let mut org_repo = OrgRepo {
+        org: org.to_owned(),
+        repo: repo.to_owned(),
+        moved_from: None,
+    };
+
+    Ok(org_repo.get::<Repository>("")?.archived)
+}
+
+/// When a commit was committed, for checks that fall back to GitHub.
+#[cfg(feature = "git")]
+pub(crate) fn commit_date(org: &str, repo: &str, sha: &str) -> Result<DateTime<Utc>> {
+    #[derive(Deserialize, Debug)]
+    struct Commit {
+        commit: CommitDetails,
+    }
+
+    #[derive(Deserialize, Debug)]
+    struct CommitDetails {
+        committer: Signature,
+    }
+
+    #[derive(Deserialize, Debug)]
+    struct Signature {
+        date: DateTime<Utc>,
+    }
+
+    let mut org_repo = OrgRepo {
+        org: org.to_owned(),
+        repo: repo.to_owned(),
+        moved_from: None,
+    };
+
+    Ok(org_repo
+        .get::<Commit>(&format!("/commits/{}", sha))?
+        .commit
+        .committer
+        .date)
+}
+
+/// Extract the version a dependabot PR title like "Bump serde from 1.0.1 to 1.0.2" bumps to.
+fn bumped_to_version(title: &str, dependency: &str) -> Option<Version> {
+    let title = title.to_lowercase();
+    let needle = format!("bump {} from ", dependency.to_lowercase());
+    let rest = &title[title.find(&needle)? + needle.len()..];
+    let (_, to) = rest.split_once(" to ")?;
+    let version = to.split_whitespace().next()?.trim_start_matches('v');
+    version.parse().ok()
+}
+
+/// Match `text` against a glob `pattern` where `*` matches any sequence of characters and `?`
+/// matches any single character.
+fn glob_matches(pattern: &str, text: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let text = text.chars().collect::<Vec<_>>();
+
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((star_p, star_t)) => {
+                    p = star_p + 1;
+                    t = star_t + 1;
+                    backtrack = Some((star_p, star_t + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
 fn github_request<B>(mut request: Request<B>) -> Result<Request<B>> {
     request.headers_mut().insert(
         ACCEPT,
@@ -84,6 +453,10 @@ fn github_request<B>(mut request: Request<B>) -> Result<Request<B>> {
             HeaderValue::from_str(&format!("Bearer {}", auth_token))
                 .context("GitHub auth token contained invalid header value")?,
         );
+    } else {
+        // GitHub responds with 404 for private repositories when not authenticated. `OrgRepo::get`
+        // makes it fatal again if the repository can be found
+        request.extensions_mut().insert(NotFoundIsntFatal);
     }
 
     Ok(request)
@@ -96,24 +469,542 @@ fn auth_token() -> Option<String> {
 }
 
 pub(crate) struct OrgRepoIssue {
-    org: String,
-    repo: String,
+    org_repo: OrgRepo,
     issue: u64,
 }
 
+/// Parses either `"org", "repo", number`, `"org/repo#number"`, or a GitHub URL such as
+/// `"https://github.com/org/repo/issues/number"`.
 impl Parse for OrgRepoIssue {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        let org = input.parse::<syn::LitStr>()?.value();
+        Ok(parse_org_repo_issue(input)?.0)
+    }
+}
+
+/// One or more issues, optionally prefixed with `any:` or `all:`.
+pub(crate) struct Issues {
+    mode: Mode,
+    issues: Vec<OrgRepoIssue>,
+}
+
+#[derive(Clone, Copy)]
+enum Mode {
+    Any,
+    All,
+}
+
+impl Parse for Issues {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        if !(input.peek(syn::Ident) && input.peek2(syn::token::Colon)) {
+            return Ok(Self {
+                mode: Mode::Any,
+                issues: vec![input.parse()?],
+            });
+        }
+
+        let ident = input.parse::<syn::Ident>()?;
+        let mode = if ident == "any" {
+            Mode::Any
+        } else if ident == "all" {
+            Mode::All
+        } else {
+            return Err(syn::Error::new(ident.span(), "expected `any` or `all`"));
+        };
+        input.parse::<syn::token::Colon>()?;
+
+        let lits =
+            syn::punctuated::Punctuated::<syn::LitStr, syn::token::Comma>::parse_terminated(input)?;
+        if lits.is_empty() {
+            return Err(input.error("expected at least one issue"));
+        }
+
+        let issues = lits
+            .iter()
+            .map(org_repo_issue_from_lit)
+            .collect::<syn::Result<_>>()?;
+
+        Ok(Self { mode, issues })
+    }
+}
+
+/// An issue, as `"org/repo#number"` or a URL, followed by a date as for `after_date!`.
+#[cfg(feature = "time")]
+pub(crate) struct IssueOrDate {
+    issue: OrgRepoIssue,
+    date: crate::time::AfterDate,
+}
+
+#[cfg(feature = "time")]
+impl Parse for IssueOrDate {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let issue = org_repo_issue_from_lit(&input.parse()?)?;
         input.parse::<syn::token::Comma>()?;
+        let date = input.parse()?;
+
+        Ok(Self { issue, date })
+    }
+}
+
+/// Like [`OrgRepoIssue`] but rejects URLs pointing at issues rather than pull requests.
+pub(crate) struct OrgRepoPullRequest(OrgRepoIssue);
+
+impl Parse for OrgRepoPullRequest {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let (org_repo_issue, first) = parse_org_repo_issue(input)?;
+
+        if let Some(UrlKind::Issue) = parse_github_url(&first.value()).map(|(_, _, kind, _)| kind) {
+            return Err(syn::Error::new(
+                first.span(),
+                "expected a pull request URL but found an issue URL. Use `issue_closed!` for issues",
+            ));
+        }
+
+        Ok(Self(org_repo_issue))
+    }
+}
+
+fn parse_org_repo_issue(
+    input: syn::parse::ParseStream,
+) -> syn::Result<(OrgRepoIssue, syn::LitStr)> {
+    let first = input.parse::<syn::LitStr>()?;
+
+    if first.value().contains(&['/', '#'][..]) {
+        let org_repo_issue = org_repo_issue_from_lit(&first)?;
+
+        input.parse::<syn::token::Comma>().ok();
+        if !input.is_empty() {
+            return Err(input.error("unexpected arguments after `org/repo#number`"));
+        }
+
+        return Ok((org_repo_issue, first));
+    }
+
+    let org = first.value();
+    input.parse::<syn::token::Comma>()?;
+
+    let repo = input.parse::<syn::LitStr>()?;
+    if repo.value().contains(&['/', '#'][..]) {
+        return Err(syn::Error::new(
+            repo.span(),
+            "expected repository name. Use either `\"org\", \"repo\", number` or `\"org/repo#number\"`",
+        ));
+    }
+    input.parse::<syn::token::Comma>()?;
+
+    let issue = input.parse::<syn::LitInt>()?.base10_parse()?;
+
+    input.parse::<syn::token::Comma>().ok();
+
+    if org.is_empty() || repo.value().is_empty() {
+        return Err(syn::Error::new(
+            first.span(),
+            "organization and repository cannot be empty",
+        ));
+    }
 
-        let repo = input.parse::<syn::LitStr>()?.value();
+    let org_repo_issue = OrgRepoIssue {
+        org_repo: OrgRepo {
+            org,
+            repo: repo.value(),
+            moved_from: None,
+        },
+        issue,
+    };
+
+    Ok((org_repo_issue, first))
+}
+
+/// Parse a single string literal containing either `org/repo#number` or a GitHub URL.
+fn org_repo_issue_from_lit(lit: &syn::LitStr) -> syn::Result<OrgRepoIssue> {
+    let value = lit.value();
+
+    let (org, repo, issue) = if is_url(&value) {
+        parse_github_url(&value)
+            .map(|(org, repo, _, issue)| (org, repo, issue))
+            .ok_or_else(|| {
+                syn::Error::new(
+                    lit.span(),
+                    "expected `https://github.com/org/repo/issues/number` or `https://github.com/org/repo/pull/number`",
+                )
+            })?
+    } else {
+        split_org_repo_issue(&value)
+            .ok_or_else(|| syn::Error::new(lit.span(), "expected `org/repo#number`"))?
+    };
+
+    if org.is_empty() || repo.is_empty() {
+        return Err(syn::Error::new(
+            lit.span(),
+            "organization and repository cannot be empty",
+        ));
+    }
+
+    Ok(OrgRepoIssue {
+        org_repo: OrgRepo {
+            org,
+            repo,
+            moved_from: None,
+        },
+        issue,
+    })
+}
+
+/// Split `"org/repo#number"` into its parts.
+fn split_org_repo_issue(value: &str) -> Option<(String, String, u64)> {
+    let (org_repo, issue) = value.split_once('#')?;
+    let (org, repo) = org_repo.split_once('/')?;
+
+    if repo.contains('/') {
+        return None;
+    }
+
+    Some((org.to_owned(), repo.to_owned(), issue.parse().ok()?))
+}
+
+#[derive(Clone, Copy)]
+enum UrlKind {
+    Issue,
+    PullRequest,
+}
+
+fn is_url(value: &str) -> bool {
+    value.starts_with("https://")
+        || value.starts_with("http://")
+        || value.starts_with("github.com/")
+}
+
+/// Split a URL like `"https://github.com/org/repo/issues/number"` into its parts.
+fn parse_github_url(value: &str) -> Option<(String, String, UrlKind, u64)> {
+    let path = value
+        .trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .trim_start_matches("www.")
+        .strip_prefix("github.com/")?;
+    let path = path.split(&['?', '#'][..]).next()?;
+
+    let mut segments = path.split('/');
+    let org = segments.next()?;
+    let repo = segments.next()?;
+    let kind = match segments.next()? {
+        "issues" => UrlKind::Issue,
+        "pull" => UrlKind::PullRequest,
+        _ => return None,
+    };
+    let issue = segments.next()?.parse().ok()?;
+
+    Some((org.to_owned(), repo.to_owned(), kind, issue))
+}
+
+pub(crate) struct OrgRepo {
+    org: String,
+    repo: String,
+    moved_from: Option<String>,
+}
+
+impl OrgRepo {
+    /// Make a `GET` request to `https://api.github.com/repos/{org}/{repo}{path}`.
+    ///
+    /// If the repository has been renamed or transferred the redirect is followed and `self` is
+    /// updated to refer to the new location.
+    fn get<T>(&mut self, path: &str) -> Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        #[derive(Deserialize, Debug)]
+        struct Repository {
+            full_name: String,
+        }
+
+        let result = request_following_redirects::<T>(github_request(
+            Request::builder()
+                .uri(format!(
+                    "https://api.github.com/repos/{}/{}{}",
+                    self.org, self.repo, path
+                ))
+                .body(())
+                .unwrap(),
+        )?);
+        let (value, uri) = match result {
+            Err(mut err) if !path.is_empty() => {
+                if let Some(not_found) = err.downcast_mut::<NotFound>() {
+                    // without a token the 404 might be because the repository is private, so
+                    // only blame the macro input if the repository itself can be found
+                    if !not_found.fatal {
+                        not_found.fatal = self.get::<serde::de::IgnoredAny>("").is_ok();
+                    }
+                }
+                return Err(err);
+            }
+            result => result?,
+        };
+
+        let mut segments = uri.path().trim_start_matches('/').split('/');
+        let full_name = match (segments.next(), segments.next(), segments.next()) {
+            (Some("repos"), Some(org), Some(repo)) => format!("{}/{}", org, repo),
+            (Some("repositories"), Some(id), _) => {
+                request::<Repository>(github_request(
+                    Request::builder()
+                        .uri(format!("https://api.github.com/repositories/{}", id))
+                        .body(())
+                        .unwrap(),
+                )?)?
+                .full_name
+            }
+            _ => return Ok(value),
+        };
+
+        if !full_name.eq_ignore_ascii_case(&self.to_string()) {
+            if let Some((org, repo)) = full_name.split_once('/') {
+                crate::warn(format!(
+                    "{} has moved to {}. Update the macro argument to use the new name.",
+                    self, full_name
+                ));
+
+                self.moved_from = Some(self.to_string());
+                self.org = org.to_owned();
+                self.repo = repo.to_owned();
+            }
+        }
+
+        Ok(value)
+    }
+
+    /// Mention that the repository has moved in `msg`, if it has.
+    fn message(&self, msg: String) -> String {
+        match &self.moved_from {
+            Some(moved_from) => format!(
+                "{}\n\n{} has moved to {}. Update the macro argument to use the new name.",
+                msg, moved_from, self
+            ),
+            None => msg,
+        }
+    }
+}
+
+impl fmt::Display for OrgRepo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.org, self.repo)
+    }
+}
+
+impl Parse for OrgRepo {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let lit = input.parse::<syn::LitStr>()?;
+
+        match lit.value().split_once('/') {
+            Some((org, repo)) if !org.is_empty() && !repo.is_empty() && !repo.contains('/') => {
+                Ok(Self {
+                    org: org.to_owned(),
+                    repo: repo.to_owned(),
+                    moved_from: None,
+                })
+            }
+            _ => Err(syn::Error::new(lit.span(), "expected `org/repo`")),
+        }
+    }
+}
+
+pub(crate) struct ReleaseAsset {
+    org_repo: OrgRepo,
+    tag: String,
+    pattern: String,
+}
+
+impl Parse for ReleaseAsset {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let org_repo = input.parse::<OrgRepo>()?;
+        input.parse::<syn::token::Comma>()?;
+
+        let tag = input.parse::<syn::LitStr>()?.value();
         input.parse::<syn::token::Comma>()?;
 
-        let issue = input.parse::<syn::LitInt>()?.base10_parse()?;
+        let pattern = input.parse::<syn::LitStr>()?.value();
 
         input.parse::<syn::token::Comma>().ok();
 
-        Ok(Self { org, repo, issue })
+        Ok(Self {
+            org_repo,
+            tag,
+            pattern,
+        })
+    }
+}
+
+pub(crate) struct GhsaPublished {
+    org_repo: OrgRepo,
+    since: Option<NaiveDate>,
+    severity: Option<Severity>,
+}
+
+impl Parse for GhsaPublished {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let org_repo = input.parse::<OrgRepo>()?;
+
+        let mut since = None;
+        let mut severity = None;
+
+        while input.parse::<syn::token::Comma>().is_ok() && !input.is_empty() {
+            let ident = input.parse::<syn::Ident>()?;
+            input.parse::<syn::token::Eq>()?;
+            let lit = input.parse::<syn::LitStr>()?;
+
+            if ident == "since" {
+                let date = NaiveDate::parse_from_str(&lit.value(), "%Y-%m-%d")
+                    .map_err(|err| syn::Error::new(lit.span(), err))?;
+                since = Some(date);
+            } else if ident == "severity" {
+                let value = match &*lit.value() {
+                    "low" => Severity::Low,
+                    "medium" => Severity::Medium,
+                    "high" => Severity::High,
+                    "critical" => Severity::Critical,
+                    _ => {
+                        return Err(syn::Error::new(
+                            lit.span(),
+                            "expected one of `low`, `medium`, `high`, or `critical`",
+                        ))
+                    }
+                };
+                severity = Some(value);
+            } else {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    "unknown argument. Expected `since` or `severity`",
+                ));
+            }
+        }
+
+        Ok(Self {
+            org_repo,
+            since,
+            severity,
+        })
+    }
+}
+
+pub(crate) struct DependabotMerged {
+    org_repo: OrgRepo,
+    dependency: String,
+    version_req: VersionReq,
+}
+
+impl Parse for DependabotMerged {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let org_repo = input.parse::<OrgRepo>()?;
+        input.parse::<syn::token::Comma>()?;
+
+        let dependency = input.parse::<syn::LitStr>()?.value();
+        input.parse::<syn::token::Comma>()?;
+
+        let lit = input.parse::<syn::LitStr>()?;
+        let version_req = lit
+            .value()
+            .parse()
+            .map_err(|err| syn::Error::new(lit.span(), err))?;
+
+        input.parse::<syn::token::Comma>().ok();
+
+        Ok(Self {
+            org_repo,
+            dependency,
+            version_req,
+        })
+    }
+}
+
+pub(crate) struct ForkBehind {
+    fork: OrgRepo,
+    upstream: OrgRepo,
+    threshold: Threshold,
+}
+
+impl Parse for ForkBehind {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let fork = input.parse::<OrgRepo>()?;
+        input.parse::<syn::token::Comma>()?;
+
+        let ident = input.parse::<syn::Ident>()?;
+        if ident != "upstream" {
+            return Err(syn::Error::new(ident.span(), "expected `upstream`"));
+        }
+        input.parse::<syn::token::Eq>()?;
+        let upstream = input.parse::<OrgRepo>()?;
+        input.parse::<syn::token::Comma>()?;
+
+        let threshold = input.parse::<Threshold>()?;
+
+        input.parse::<syn::token::Comma>().ok();
+
+        Ok(Self {
+            fork,
+            upstream,
+            threshold,
+        })
+    }
+}
+
+/// The number of an RFC, which is the number of its pull request in rust-lang/rfcs.
+pub(crate) struct Rfc {
+    number: u64,
+}
+
+impl Parse for Rfc {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let number = input.parse::<syn::LitInt>()?.base10_parse()?;
+
+        input.parse::<syn::token::Comma>().ok();
+
+        Ok(Self { number })
+    }
+}
+
+/// An action such as `"actions/checkout"` and a version requirement.
+///
+/// Actions in a subdirectory of a repository, such as `"github/codeql-action/init"`, share the
+/// repository's tags so only the `org/repo` part is used.
+pub(crate) struct GithubAction {
+    org_repo: OrgRepo,
+    version_req: VersionReq,
+}
+
+impl Parse for GithubAction {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let lit = input.parse::<syn::LitStr>()?;
+        let value = lit.value();
+
+        let mut segments = value.split('/');
+        let org_repo = match (segments.next(), segments.next()) {
+            (Some(org), Some(repo))
+                if !org.is_empty() && !repo.is_empty() && !value.contains('@') =>
+            {
+                OrgRepo {
+                    org: org.to_owned(),
+                    repo: repo.to_owned(),
+                    moved_from: None,
+                }
+            }
+            _ => {
+                return Err(syn::Error::new(
+                    lit.span(),
+                    "expected `org/repo` or `org/repo/path`",
+                ))
+            }
+        };
+
+        input.parse::<syn::token::Comma>()?;
+
+        let lit = input.parse::<syn::LitStr>()?;
+        let version_req = lit
+            .value()
+            .parse()
+            .map_err(|err| syn::Error::new(lit.span(), err))?;
+
+        input.parse::<syn::token::Comma>().ok();
+
+        Ok(Self {
+            org_repo,
+            version_req,
+        })
     }
 }
 
@@ -124,12 +1015,48 @@ impl Parse for OrgRepoIssue {
 /// todo_or_die::issue_closed!("tokio-rs", "axum", 1);
 /// ```
 ///
+/// closed issue with `org/repo#number`
+/// ```compile_fail
+/// todo_or_die::issue_closed!("tokio-rs/axum#1");
+/// ```
+///
+/// closed issue with URL
+/// ```compile_fail
+/// todo_or_die::issue_closed!("https://github.com/tokio-rs/axum/issues/1");
+/// ```
+///
+/// any of several issues closed
+/// ```compile_fail
+/// todo_or_die::issue_closed!(any: "tokio-rs/axum#1", "rust-lang/rust#1563");
+/// ```
+///
+/// not all of several issues closed
+/// ```
+/// todo_or_die::issue_closed!(all: "tokio-rs/axum#1", "rust-lang/rust#1563");
+/// ```
+///
 /// open issue
 /// ```
 /// // the oldest open rust-lang issue. Probably wont be close anytime soon :shrug:
 /// todo_or_die::issue_closed!("rust-lang", "rust", 1563);
 /// ```
 ///
+/// issue that doesn't exist
+/// ```compile_fail
+/// todo_or_die::issue_closed!("tokio-rs", "axum", 99999999);
+/// ```
+///
+/// as an attribute
+/// ```compile_fail
+/// #[todo_or_die::when(issue_closed("tokio-rs/axum#1"))]
+/// fn workaround() {}
+/// ```
+///
+/// ```
+/// #[todo_or_die::when(issue_closed("rust-lang/rust#1563"))]
+/// fn workaround() {}
+/// ```
+///
 /// # `pr_closed`
 ///
 /// closed pr
@@ -146,5 +1073,159 @@ impl Parse for OrgRepoIssue {
 /// ```
 /// todo_or_die::pr_closed!("davidpdrsn", "keep", 1);
 /// ```
+///
+/// open pr with `org/repo#number`
+/// ```
+/// todo_or_die::pr_closed!("davidpdrsn/keep#1");
+/// ```
+///
+/// open pr with URL
+/// ```
+/// todo_or_die::pr_closed!("https://github.com/davidpdrsn/keep/pull/1");
+/// ```
+///
+/// as an attribute
+/// ```compile_fail
+/// #[todo_or_die::when(pr_closed("tokio-rs/axum#266"))]
+/// mod workaround {}
+/// ```
+///
+/// ```
+/// #[todo_or_die::when(pr_closed("davidpdrsn/keep#1"))]
+/// mod workaround {}
+/// ```
+///
+/// issue URL given to `pr_closed`
+/// ```compile_fail
+/// todo_or_die::pr_closed!("https://github.com/davidpdrsn/keep/issues/1");
+/// ```
+///
+/// mixing both forms
+/// ```compile_fail
+/// todo_or_die::pr_closed!("davidpdrsn/keep#1", "keep", 1);
+/// ```
+///
+/// missing issue number
+/// ```compile_fail
+/// todo_or_die::pr_closed!("davidpdrsn/keep");
+/// ```
+///
+/// # `release_asset`
+///
+/// release with matching asset
+/// ```compile_fail
+/// todo_or_die::release_asset!("BurntSushi/ripgrep", "14.1.0", "*-x86_64-unknown-linux-musl.tar.gz");
+/// ```
+///
+/// release without matching asset
+/// ```
+/// todo_or_die::release_asset!("BurntSushi/ripgrep", "14.1.0", "*.definitely-not-shipped");
+/// ```
+///
+/// # `ghsa_published`
+///
+/// repo with published advisories
+/// ```compile_fail
+/// todo_or_die::ghsa_published!("tokio-rs/tokio");
+/// ```
+///
+/// no advisories after the baseline date
+/// ```
+/// todo_or_die::ghsa_published!("tokio-rs/tokio", since = "3000-01-01", severity = "critical");
+/// ```
+///
+/// # `dependabot_merged`
+///
+/// merged dependabot pr
+/// ```compile_fail
+/// todo_or_die::dependabot_merged!("tokio-rs/axum", "tower-http", ">=0.1");
+/// ```
+///
+/// no matching dependabot pr
+/// ```
+/// todo_or_die::dependabot_merged!("tokio-rs/axum", "tower-http", ">=1000.0");
+/// ```
+///
+/// dependency names that need escaping
+/// ```
+/// todo_or_die::dependabot_merged!("tokio-rs/axum", "@types/node + c++", ">=1000.0");
+/// ```
+///
+/// # `fork_behind`
+///
+/// fork far behind upstream
+/// ```compile_fail
+/// todo_or_die::fork_behind!("davidpdrsn/tokio", upstream = "tokio-rs/tokio", "> 50 commits");
+/// ```
+///
+/// fork not far enough behind upstream
+/// ```
+/// todo_or_die::fork_behind!("davidpdrsn/tokio", upstream = "tokio-rs/tokio", "> 1000000 commits");
+/// ```
+///
+/// # `github_action`
+///
+/// new major version released
+/// ```compile_fail
+/// todo_or_die::github_action!("actions/checkout", ">=4");
+/// ```
+///
+/// no such version yet
+/// ```
+/// todo_or_die::github_action!("actions/checkout", ">=1000");
+/// ```
+///
+/// action in a subdirectory
+/// ```compile_fail
+/// todo_or_die::github_action!("github/codeql-action/init", ">=3");
+/// ```
+///
+/// pinned ref isn't allowed
+/// ```compile_fail
+/// todo_or_die::github_action!("actions/checkout@v4", ">=1000");
+/// ```
+///
+/// # `rfc_merged`
+///
+/// merged RFC
+/// ```compile_fail
+/// todo_or_die::rfc_merged!(1598);
+/// ```
+///
+/// string rather than a number
+/// ```compile_fail
+/// todo_or_die::rfc_merged!("1598");
+/// ```
+///
+/// # `issue_or_date`
+///
+/// ```compile_fail
+/// todo_or_die::issue_or_date!("tokio-rs/axum#1", 3000, 01, 01);
+/// ```
+///
+/// ```compile_fail
+/// todo_or_die::issue_or_date!("tokio-rs/axum#9999999", "1990-01-01");
+/// ```
+///
+/// ```
+/// todo_or_die::issue_or_date!("rust-lang/rust#1563", 3000, 01, 01, "Europe/Stockholm");
+/// ```
+///
+/// ```compile_fail
+/// todo_or_die::issue_or_date!("https://github.com/tokio-rs/axum/issues/1", 3000, 01, 01);
+/// ```
+///
+/// ```
+/// todo_or_die::issue_or_date!(
+///     "rust-lang/rust#1563",
+///     warn: (3000, 01, 01),
+///     die: (3001, 01, 01),
+/// );
+/// ```
+///
+/// missing date
+/// ```compile_fail
+/// todo_or_die::issue_or_date!("tokio-rs/axum#1");
+/// ```
 #[allow(dead_code)]
 fn tests() {}
@@ -0,0 +1,60 @@
+use crate::{http::request, package};
+use anyhow::{Context as _, Result};
+use hyper::Request;
+use serde::Deserialize;
+
+pub(crate) fn go_module(input: package::Input) -> Result<Option<String>> {
+    #[derive(Debug, Deserialize)]
+    struct Response {
+        #[serde(rename = "Version")]
+        version: String,
+    }
+
+    let data = request::<Response>(
+        Request::builder()
+            .uri(format!(
+                "https://proxy.golang.org/{}/@latest",
+                escape_module_path(&input.name)
+            ))
+            .body(())
+            .unwrap(),
+    )?;
+
+    let latest_version =
+        package::parse_version(&data.version).context("Couldn't parse latest version")?;
+
+    Ok(package::check(
+        &input,
+        "the Go module proxy",
+        &latest_version,
+    ))
+}
+
+/// The module proxy requires upper case letters to be escaped as `!` followed by the lower case
+/// letter.
+fn escape_module_path(path: &str) -> String {
+    let mut out = String::with_capacity(path.len());
+    for c in path.chars() {
+        if c.is_ascii_uppercase() {
+            out.push('!');
+            out.push(c.to_ascii_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// ```compile_fail
+/// todo_or_die::go_module!("github.com/hashicorp/terraform", ">=1.0");
+/// ```
+///
+/// ```
+/// todo_or_die::go_module!("github.com/hashicorp/terraform", ">=1000");
+/// ```
+///
+/// ```compile_fail
+/// todo_or_die::go_module!("github.com/BurntSushi/toml", ">=1.0");
+/// ```
+#[allow(dead_code)]
+fn tests() {}
@@ -0,0 +1,133 @@
+use crate::Outcome;
+use anyhow::{Context as _, Result};
+use chrono::{DateTime, Duration, Utc};
+use std::{io::ErrorKind, path::PathBuf};
+
+/// Delay failing the build until `grace_days` after the check first failed.
+///
+/// The first time a check fails we record the time, keyed by `key` which identifies the check.
+/// Until the grace period is over the compile error is downgraded to a warning. The record is
+/// removed when the check passes, so the grace period starts over if it fails again later.
+pub(crate) fn apply(key: &Key, grace_days: u32, outcome: Outcome) -> Result<Outcome> {
+    let msg = match outcome {
+        Outcome::Die(msg) => msg,
+        outcome => {
+            key.remove_record()?;
+            return Ok(outcome);
+        }
+    };
+
+    let triggered_at = match key.record()? {
+        Some(record) => DateTime::parse_from_rfc3339(record.trim())
+            .context("Failed to parse grace period record")?
+            .with_timezone(&Utc),
+        None => {
+            let now = crate::clock::now()?;
+            key.insert_record(&now.to_rfc3339())?;
+            now
+        }
+    };
+
+    let deadline = triggered_at
+        .checked_add_signed(Duration::days(grace_days.into()))
+        .context("Grace period ends too far in the future")?;
+    if crate::clock::now()? < deadline {
+        Ok(Outcome::Warn(format!(
+            "{} This will fail to compile from {}.",
+            msg,
+            deadline.format("%Y-%m-%d %H:%M UTC")
+        )))
+    } else {
+        Ok(Outcome::Die(msg))
+    }
+}
+
+/// Identifies a check across builds.
+pub(crate) enum Key {
+    /// The check's `id`. The record is kept in the crate's `.todo-or-die` file, which is meant to
+    /// be committed, so it's shared with CI.
+    Id(String),
+    /// The macro's arguments. The record is kept in a temporary directory, so it's local to this
+    /// machine.
+    Input(String),
+}
+
+impl Key {
+    fn record(&self) -> Result<Option<String>> {
+        match self {
+            Key::Id(id) => crate::marker::get(&marker_key(id)),
+            Key::Input(input) => match std::fs::read_to_string(local_record_path(input)?) {
+                Ok(contents) => Ok(Some(contents)),
+                Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+                Err(err) => Err(err).context("Failed to read grace period record"),
+            },
+        }
+    }
+
+    fn insert_record(&self, record: &str) -> Result<()> {
+        match self {
+            Key::Id(id) => crate::marker::insert(&marker_key(id), record),
+            Key::Input(input) => std::fs::write(local_record_path(input)?, record)
+                .context("Failed to write grace period record"),
+        }
+    }
+
+    fn remove_record(&self) -> Result<()> {
+        match self {
+            Key::Id(id) => crate::marker::remove(&marker_key(id)),
+            Key::Input(input) => match std::fs::remove_file(local_record_path(input)?) {
+                Ok(()) => Ok(()),
+                Err(err) if err.kind() == ErrorKind::NotFound => Ok(()),
+                Err(err) => Err(err).context("Failed to remove grace period record"),
+            },
+        }
+    }
+}
+
+/// Ids can't contain `.` so this never clashes with the ids used by `dies_in!`.
+fn marker_key(id: &str) -> String {
+    format!("grace.{}", id)
+}
+
+fn local_record_path(input: &str) -> Result<PathBuf> {
+    // FNV-1a, since unlike `DefaultHasher` its output doesn't change between Rust versions
+    let hash = input.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+
+    // not in the HTTP cache dir, so clearing the cache doesn't restart grace periods
+    let dir = std::env::temp_dir().join("todo_or_die_grace");
+    std::fs::create_dir_all(&dir).context("Failed to create dir to store grace periods")?;
+    Ok(dir.join(format!("{:016x}", hash)))
+}
+
+/// ```
+/// todo_or_die::llvm_version!("<1", grace_days = 14);
+/// ```
+///
+/// combined with other modifiers
+/// ```
+/// todo_or_die::llvm_version!("<1", grace_days = 14, only(unix), only_release);
+/// ```
+///
+/// duplicate `grace_days`
+/// ```compile_fail
+/// todo_or_die::llvm_version!("<1", grace_days = 14, grace_days = 1);
+/// ```
+///
+/// ```compile_fail
+/// todo_or_die::llvm_version!("<1", grace_days = "14");
+/// ```
+///
+/// too many days
+/// ```compile_fail
+/// todo_or_die::llvm_version!("<1", grace_days = 4000000000);
+/// ```
+///
+/// checks that don't make network requests
+/// ```compile_fail
+/// todo_or_die::after_date!(3000, 01, 01, grace_days = 3);
+/// ```
+#[cfg(feature = "rust")]
+#[allow(dead_code)]
+fn tests() {}
@@ -0,0 +1,94 @@
+use crate::{http::request_text, package};
+use anyhow::{Context as _, Result};
+use hyper::Request;
+use serde::Deserialize;
+use std::collections::HashMap;
+use syn::parse::Parse;
+
+pub(crate) fn helm_chart(input: Input) -> Result<Option<String>> {
+    #[derive(Debug, Deserialize)]
+    struct Index {
+        entries: HashMap<String, Vec<Chart>>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Chart {
+        version: String,
+    }
+
+    let repo = input.repo.trim_end_matches('/');
+
+    let text = request_text(
+        Request::builder()
+            .uri(format!("{}/index.yaml", repo))
+            .body(())
+            .unwrap(),
+    )?;
+
+    let index = serde_yaml::from_str::<Index>(&text).context("Failed to parse index.yaml")?;
+
+    let charts = match index.entries.get(&input.package.name) {
+        Some(charts) => charts,
+        None => {
+            return Err(package::NotInRegistry {
+                name: input.package.name.clone(),
+                registry: repo.to_owned(),
+            }
+            .into())
+        }
+    };
+
+    let latest_version = package::latest_stable(charts.iter().map(|chart| &chart.version))
+        .with_context(|| {
+            format!(
+                "Chart `{}` in {} has no stable versions",
+                input.package.name, repo
+            )
+        })?;
+
+    Ok(package::check(&input.package, repo, &latest_version))
+}
+
+pub(crate) struct Input {
+    repo: String,
+    package: package::Input,
+}
+
+impl Parse for Input {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let lit = input.parse::<syn::LitStr>()?;
+        let repo = lit.value();
+        if !repo.starts_with("https://") && !repo.starts_with("http://") {
+            return Err(syn::Error::new(
+                lit.span(),
+                "expected the URL of a chart repository",
+            ));
+        }
+
+        input.parse::<syn::token::Comma>()?;
+
+        let package = input.parse::<package::Input>()?;
+
+        Ok(Self { repo, package })
+    }
+}
+
+/// ```compile_fail
+/// todo_or_die::helm_chart!("https://charts.bitnami.com/bitnami", "postgresql", ">=10");
+/// ```
+///
+/// ```
+/// todo_or_die::helm_chart!("https://charts.bitnami.com/bitnami", "postgresql", ">=1000");
+/// ```
+///
+/// trailing slash
+/// ```compile_fail
+/// todo_or_die::helm_chart!("https://charts.bitnami.com/bitnami/", "postgresql", ">=10");
+/// ```
+///
+/// unknown chart
+/// ```compile_fail
+/// todo_or_die::helm_chart!("https://charts.bitnami.com/bitnami", "no-such-chart", ">=0");
+/// ```
+#[allow(dead_code)]
+fn tests() {}
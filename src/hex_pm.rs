@@ -0,0 +1,36 @@
+use crate::{http::request, package};
+use anyhow::{Context as _, Result};
+use hyper::Request;
+use serde::Deserialize;
+
+pub(crate) fn hex_pm(input: package::Input) -> Result<Option<String>> {
+    #[derive(Debug, Deserialize)]
+    struct Response {
+        latest_stable_version: Option<String>,
+    }
+
+    let data = request::<Response>(
+        Request::builder()
+            .uri(format!("https://hex.pm/api/packages/{}", input.name))
+            .body(())
+            .unwrap(),
+    )?;
+
+    let latest_version = data
+        .latest_stable_version
+        .as_deref()
+        .and_then(package::parse_version)
+        .context("No stable versions found for package")?;
+
+    Ok(package::check(&input, "Hex", &latest_version))
+}
+
+/// ```compile_fail
+/// todo_or_die::hex_pm!("phoenix", ">=1.0");
+/// ```
+///
+/// ```
+/// todo_or_die::hex_pm!("phoenix", ">=1000");
+/// ```
+#[allow(dead_code)]
+fn tests() {}
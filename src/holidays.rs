@@ -0,0 +1,230 @@
+use crate::config::ConfigError;
+use anyhow::{Context as _, Result};
+use chrono::{Datelike as _, NaiveDate, Weekday};
+use std::collections::HashMap;
+use syn::parse::Parse;
+
+/// Where to get holidays from, given as `holidays = ...`.
+pub(crate) enum Holidays {
+    /// A list of dates, such as `["2025-12-24", (2025, 12, 25)]`.
+    Dates(Vec<NaiveDate>),
+    /// The public holidays of a country, such as `"US"`.
+    #[cfg_attr(not(feature = "holidays"), allow(dead_code))]
+    Country(String),
+    /// An iCalendar file, either a URL or a path relative to the crate root.
+    Calendar(String),
+}
+
+impl Holidays {
+    /// Start looking up holidays.
+    pub(crate) fn calendar(&self) -> HolidayCalendar<'_> {
+        HolidayCalendar {
+            holidays: self,
+            by_year: HashMap::new(),
+        }
+    }
+
+    fn dates_in_year(&self, year: i32) -> Result<Vec<NaiveDate>> {
+        let dates = match self {
+            Holidays::Dates(dates) => dates.clone(),
+            #[cfg(feature = "holidays")]
+            Holidays::Country(country) => {
+                or_weekends_only(country_holidays(country, year), country)?
+            }
+            #[cfg(not(feature = "holidays"))]
+            Holidays::Country(_) => unreachable!("rejected while parsing"),
+            Holidays::Calendar(source) => calendar_holidays(source)?,
+        };
+
+        Ok(dates
+            .into_iter()
+            .filter(|date| date.year() == year)
+            .collect())
+    }
+}
+
+/// Looks up holidays, fetching each year at most once.
+pub(crate) struct HolidayCalendar<'a> {
+    holidays: &'a Holidays,
+    by_year: HashMap<i32, Vec<NaiveDate>>,
+}
+
+impl HolidayCalendar<'_> {
+    pub(crate) fn is_business_day(&mut self, date: NaiveDate) -> Result<bool> {
+        if matches!(date.weekday(), Weekday::Sat | Weekday::Sun) {
+            return Ok(false);
+        }
+
+        let holidays = match self.by_year.get(&date.year()) {
+            Some(holidays) => holidays,
+            None => {
+                let holidays = self.holidays.dates_in_year(date.year())?;
+                self.by_year.entry(date.year()).or_insert(holidays)
+            }
+        };
+
+        Ok(!holidays.contains(&date))
+    }
+
+    /// Move `date` forward until it's a business day.
+    pub(crate) fn roll_forward(&mut self, mut date: NaiveDate) -> Result<NaiveDate> {
+        while !self.is_business_day(date)? {
+            date = date.succ_opt().context("Date out of range")?;
+        }
+        Ok(date)
+    }
+}
+
+impl Parse for Holidays {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        if input.peek(syn::token::Bracket) {
+            let content;
+            syn::bracketed!(content in input);
+
+            let mut dates = Vec::new();
+            while !content.is_empty() {
+                let date = crate::time::parse_date_arg(&content)?;
+                dates.push(date.to_naive_date());
+
+                if content.parse::<syn::token::Comma>().is_err() {
+                    break;
+                }
+            }
+
+            return Ok(Holidays::Dates(dates));
+        }
+
+        let lit = input.parse::<syn::LitStr>()?;
+        let value = lit.value();
+
+        let is_country = value.len() == 2 && value.chars().all(|c| c.is_ascii_uppercase());
+        let is_url = value.starts_with("https://") || value.starts_with("http://");
+        if (is_country || is_url) && cfg!(not(feature = "holidays")) {
+            return Err(syn::Error::new(
+                lit.span(),
+                "looking up holidays by country or URL requires the `holidays` feature",
+            ));
+        }
+
+        if is_country {
+            Ok(Holidays::Country(value))
+        } else {
+            Ok(Holidays::Calendar(value))
+        }
+    }
+}
+
+#[cfg(feature = "holidays")]
+fn country_holidays(country: &str, year: i32) -> Result<Vec<NaiveDate>> {
+    #[derive(serde::Deserialize)]
+    struct Holiday {
+        date: NaiveDate,
+    }
+
+    let holidays = crate::http::request::<Vec<Holiday>>(
+        hyper::Request::builder()
+            .uri(format!(
+                "https://date.nager.at/api/v3/PublicHolidays/{}/{}",
+                year, country
+            ))
+            .body(())
+            .unwrap(),
+    )?;
+
+    Ok(holidays.into_iter().map(|holiday| holiday.date).collect())
+}
+
+fn calendar_holidays(source: &str) -> Result<Vec<NaiveDate>> {
+    #[cfg(feature = "holidays")]
+    {
+        if source.starts_with("https://") || source.starts_with("http://") {
+            let ical =
+                (|| crate::http::request_text(hyper::Request::builder().uri(source).body(())?))();
+            return or_weekends_only(ical.map(|ical| parse_ical(&ical)), source);
+        }
+    }
+
+    let dir = std::env::var("CARGO_MANIFEST_DIR").context("`CARGO_MANIFEST_DIR` isn't set")?;
+    let path = std::path::Path::new(&dir).join(source);
+    let ical = std::fs::read_to_string(&path).map_err(|err| {
+        ConfigError(format!(
+            "Failed to read holidays from {}: {}",
+            path.display(),
+            err
+        ))
+    })?;
+    Ok(parse_ical(&ical))
+}
+
+/// Holidays fetched over the network fall back to only skipping weekends if the request fails,
+/// rather than the error making the check pass.
+///
+/// Resources that don't exist are still reported since that means the input is wrong.
+#[cfg(feature = "holidays")]
+fn or_weekends_only(holidays: Result<Vec<NaiveDate>>, source: &str) -> Result<Vec<NaiveDate>> {
+    match holidays {
+        Ok(holidays) => Ok(holidays),
+        Err(err) if err.is::<crate::http::NotFound>() => Err(err),
+        Err(err) => {
+            crate::warn(format!(
+                "Failed to look up holidays for {:?} so only weekends are skipped: {:#}",
+                source, err
+            ));
+            Ok(Vec::new())
+        }
+    }
+}
+
+/// The start dates of the events in an iCalendar file.
+///
+/// Only `DTSTART` is used, so recurring events only count on their first occurrence.
+fn parse_ical(ical: &str) -> Vec<NaiveDate> {
+    ical.lines()
+        .filter(|line| line.starts_with("DTSTART"))
+        .filter_map(|line| line.rsplit(':').next())
+        .filter_map(|value| value.trim().get(..8))
+        .filter_map(|date| NaiveDate::parse_from_str(date, "%Y%m%d").ok())
+        .collect()
+}
+
+/// ```compile_fail
+/// todo_or_die::after_date!(1990, 01, 01, holidays = ["1990-01-01"]);
+/// ```
+///
+/// ```
+/// todo_or_die::after_date!(3000, 01, 01, "Europe/Stockholm", holidays = [(3000, 01, 01)]);
+/// ```
+///
+/// with holidays
+/// ```
+/// todo_or_die::before_date!(1990, 01, 01, holidays = ["1990-01-01"]);
+/// ```
+///
+/// missing iCalendar file
+/// ```compile_fail
+/// todo_or_die::after_date!(1990, 01, 01, holidays = "no-such-file.ics");
+/// ```
+///
+/// ```compile_fail
+/// todo_or_die::after_duration!(from = "1990-01-01", days = 10, "UTC", holidays = "US");
+/// ```
+///
+/// ```compile_fail
+/// todo_or_die::after_business_days!(from = "1990-01-01", days = 10, holidays = "US");
+/// ```
+///
+/// ```
+/// todo_or_die::after_business_days!(from = "3000-01-01", days = 10, holidays = [(3000, 01, 02)]);
+/// ```
+///
+/// duplicate `holidays`
+/// ```compile_fail
+/// todo_or_die::after_date!(3000, 01, 01, holidays = [], holidays = []);
+/// ```
+///
+/// invalid holiday
+/// ```compile_fail
+/// todo_or_die::after_date!(3000, 01, 01, holidays = ["christmas"]);
+/// ```
+#[allow(dead_code)]
+fn tests() {}
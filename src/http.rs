@@ -4,19 +4,21 @@ use hyper::{
     body::Bytes,
     client::{connect::dns::GaiResolver, HttpConnector},
     header::HeaderValue,
-    header::USER_AGENT,
-    Body, Client, Request, Response,
+    header::{AUTHORIZATION, LOCATION, USER_AGENT},
+    Body, Client, Request, Response, StatusCode, Uri,
 };
 use hyper_rustls::HttpsConnector;
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use std::{
     collections::{hash_map::DefaultHasher, HashMap},
+    fmt,
     hash::{Hash, Hasher},
     path::PathBuf,
 };
 use tokio::runtime::Runtime;
 
+#[allow(dead_code)]
 pub(crate) fn request<T>(
     // the request body isn't used in the cache key, so require it to be `()` so
     // we can guarantee that its empty
@@ -25,6 +27,61 @@ pub(crate) fn request<T>(
 where
     T: serde::de::DeserializeOwned,
 {
+    request_following_redirects(request).map(|(value, _)| value)
+}
+
+/// Like [`request`] but returns `None` if the resource doesn't exist.
+///
+/// Useful for checks that wait for something to be created.
+#[allow(dead_code)]
+pub(crate) fn request_optional<T>(request: Request<()>) -> Result<Option<T>>
+where
+    T: serde::de::DeserializeOwned,
+{
+    match request_following_redirects(request) {
+        Ok((value, _)) => Ok(Some(value)),
+        Err(err) if err.is::<NotFound>() => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+/// Like [`request`] but also returns the URI of the final response, after any redirects have been
+/// followed.
+pub(crate) fn request_following_redirects<T>(request: Request<()>) -> Result<(T, Uri)>
+where
+    T: serde::de::DeserializeOwned,
+{
+    let (body, uri) = request_bytes(request)?;
+    let value = serde_json::from_slice::<T>(&body).context("Failed to parse response")?;
+    Ok((value, uri))
+}
+
+/// Like [`request`] but returns the response body as text, for responses that aren't JSON.
+#[allow(dead_code)]
+pub(crate) fn request_text(request: Request<()>) -> Result<String> {
+    let (body, _) = request_bytes(request)?;
+    String::from_utf8(body.to_vec()).context("Response wasn't valid UTF-8")
+}
+
+/// Percent-encode `value` so it can be interpolated into a URL as a single path segment or query
+/// parameter.
+#[allow(dead_code)]
+pub(crate) fn percent_encode(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                out.push(byte as char)
+            }
+            _ => out.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    out
+}
+
+fn request_bytes(request: Request<()>) -> Result<(Bytes, Uri)> {
+    const MAX_REDIRECTS: usize = 5;
+
     RUNTIME.block_on(async move {
         if should_clear_cache() {
             clear_cache().ok();
@@ -36,31 +93,186 @@ where
             .headers_mut()
             .insert(USER_AGENT, HeaderValue::from_static("todo-or-die"));
 
-        let hash = hash_request(&request);
-
-        let response = if let Some(cached_response) =
-            cached_response(&hash).context("Failed to read cached response")?
-        {
-            cached_response
-        } else {
-            execute_request_and_cache_response(request, &hash).await?
-        };
-
-        if !response.status().is_success() {
-            let body = String::from_utf8_lossy(response.body());
-            anyhow::bail!(
-                "Received non-success response. status={}, body={:?}",
-                response.status(),
-                body
-            );
+        let mut redirects = 0;
+
+        loop {
+            let uri = request.uri().clone();
+            let hash = hash_request(&request);
+
+            let response = if let Some(cached_response) =
+                cached_response(&hash).context("Failed to read cached response")?
+            {
+                cached_response
+            } else if is_offline() {
+                return Err(Offline.into());
+            } else {
+                execute_request_and_cache_response(clone_request(&request), &hash).await?
+            };
+
+            if response.status().is_redirection() && redirects < MAX_REDIRECTS {
+                if let Some(location) = response.headers().get(LOCATION) {
+                    let location = location
+                        .to_str()
+                        .context("Redirect location contained invalid header value")?;
+
+                    follow_redirect(&mut request, location)?;
+                    redirects += 1;
+                    continue;
+                }
+            }
+
+            if response.status() == StatusCode::NOT_FOUND || response.status() == StatusCode::GONE {
+                return Err(NotFound {
+                    status: response.status(),
+                    uri,
+                    fatal: request.extensions().get::<NotFoundIsntFatal>().is_none(),
+                }
+                .into());
+            }
+
+            if !response.status().is_success() {
+                let body = String::from_utf8_lossy(response.body());
+                anyhow::bail!(
+                    "Received non-success response. status={}, body={:?}",
+                    response.status(),
+                    body
+                );
+            }
+
+            return Ok((response.into_body(), uri));
         }
-
-        let value =
-            serde_json::from_slice::<T>(&*response.body()).context("Failed to parse response")?;
-        Ok(value)
     })
 }
 
+/// Fail with [`Offline`] if we don't have network access.
+///
+/// For checks that use the network without going through [`request`].
+#[allow(dead_code)]
+pub(crate) fn ensure_online() -> Result<()> {
+    if is_offline() {
+        return Err(Offline.into());
+    }
+    Ok(())
+}
+
+/// Whether we're most likely being built without network access, because `CARGO_NET_OFFLINE` is
+/// set or we're in the Nix build sandbox.
+fn is_offline() -> bool {
+    crate::is_env_flag_set("CARGO_NET_OFFLINE") || std::env::var_os("NIX_BUILD_TOP").is_some()
+}
+
+/// The request wasn't made since we don't have network access.
+///
+/// Like building on docs.rs, this is expected so the check is skipped without reporting anything.
+#[derive(Debug)]
+pub(crate) struct Offline;
+
+impl fmt::Display for Offline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Network access isn't available")
+    }
+}
+
+impl std::error::Error for Offline {}
+
+/// Point `request` at the redirect `location`, which may be relative to the current URI.
+///
+/// Credentials are only meant for the host they were given for, so `Authorization` is dropped if
+/// the redirect goes to a different scheme, host, or port.
+fn follow_redirect(request: &mut Request<Body>, location: &str) -> Result<()> {
+    let location = resolve_location(request.uri(), location)?;
+
+    if origin(&location) != origin(request.uri()) {
+        request.headers_mut().remove(AUTHORIZATION);
+    }
+
+    *request.uri_mut() = location;
+    Ok(())
+}
+
+fn resolve_location(base: &Uri, location: &str) -> Result<Uri> {
+    let scheme = base.scheme_str().unwrap_or("https");
+    let authority = base.authority().map_or("", |authority| authority.as_str());
+
+    let has_scheme = location.find("://").is_some_and(|idx| {
+        idx > 0
+            && location[..idx]
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+    });
+
+    let resolved = if has_scheme {
+        location.to_string()
+    } else if location.starts_with("//") {
+        format!("{}:{}", scheme, location)
+    } else if location.starts_with('/') {
+        format!("{}://{}{}", scheme, authority, location)
+    } else if location.starts_with('?') {
+        format!("{}://{}{}{}", scheme, authority, base.path(), location)
+    } else {
+        let path = base.path();
+        let dir = &path[..path.rfind('/').map_or(0, |idx| idx + 1)];
+        format!("{}://{}{}{}", scheme, authority, dir, location)
+    };
+
+    resolved
+        .parse::<Uri>()
+        .with_context(|| format!("Failed to parse redirect location {:?}", location))
+}
+
+fn origin(uri: &Uri) -> (Option<&str>, Option<String>, Option<u16>) {
+    let port = uri.port_u16().or_else(|| match uri.scheme_str() {
+        Some("http") => Some(80),
+        Some("https") => Some(443),
+        _ => None,
+    });
+    (
+        uri.scheme_str(),
+        uri.host().map(|host| host.to_ascii_lowercase()),
+        port,
+    )
+}
+
+/// The requested resource doesn't exist.
+///
+/// Unlike other errors this most likely means the macro was given the wrong input, rather than
+/// there being some transient network issue, so it's reported as a compile error. Unless the
+/// request was marked with [`NotFoundIsntFatal`].
+#[derive(Debug)]
+pub(crate) struct NotFound {
+    status: StatusCode,
+    uri: Uri,
+    pub(crate) fatal: bool,
+}
+
+impl fmt::Display for NotFound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Received {} from {}.", self.status, self.uri)?;
+        if self.fatal {
+            write!(f, " Double check the arguments to the macro.")?;
+        }
+        Ok(())
+    }
+}
+
+/// Request extension for requests where a 404 doesn't mean the macro was given the wrong input,
+/// so [`NotFound`] shouldn't be a compile error.
+///
+/// For example requests to fixed URLs, which break if the resource is moved upstream, or requests
+/// made without credentials, which 404 for private resources.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct NotFoundIsntFatal;
+
+impl std::error::Error for NotFound {}
+
+fn clone_request(request: &Request<Body>) -> Request<Body> {
+    let mut out = Request::new(Body::empty());
+    *out.method_mut() = request.method().clone();
+    *out.uri_mut() = request.uri().clone();
+    *out.headers_mut() = request.headers().clone();
+    out
+}
+
 async fn execute_request_and_cache_response(
     request: Request<Body>,
     hash: &RequestHash,
@@ -108,7 +320,7 @@ fn http_client() -> &'static HyperTlsClient {
         hyper::Client::builder().build::<_, Body>(hyper_rustls::HttpsConnector::from((http, tls)))
     });
 
-    &*CLIENT
+    &CLIENT
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -168,7 +380,7 @@ fn serialize_response(response: &Response<Bytes>) -> Result<Vec<u8>> {
         status: response.status().as_u16(),
         headers,
         body: response.body().to_vec(),
-        expires_at: Local::now() + cache_ttl(),
+        expires_at: (crate::clock::now()? + cache_ttl()).with_timezone(&Local),
     };
 
     Ok(serde_json::to_vec(&response)?)
@@ -188,7 +400,7 @@ fn deserialize_response(data: Vec<u8>) -> Result<Option<Response<Bytes>>> {
         .context("Failed to deserialize cached HTTP response")?;
 
     let expires_at = response.expires_at.timestamp();
-    let now = Local::now().timestamp();
+    let now = crate::clock::now()?.timestamp();
     if now > expires_at {
         return Ok(None);
     }
@@ -222,7 +434,7 @@ struct SerializedResponse {
     expires_at: DateTime<Local>,
 }
 
-fn top_level_cache_dir() -> Result<PathBuf> {
+pub(crate) fn top_level_cache_dir() -> Result<PathBuf> {
     let path = std::env::temp_dir().join("todo_or_die_cache");
     std::fs::create_dir_all(&path).context("Failed to create dir to store HTTP caches")?;
     Ok(path)
@@ -242,3 +454,65 @@ fn caching_enabled() -> bool {
 fn should_clear_cache() -> bool {
     std::env::var("TODO_OR_DIE_CLEAR_HTTP_CACHE").is_ok()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request_with_token(uri: &str) -> Request<Body> {
+        Request::builder()
+            .uri(uri)
+            .header(AUTHORIZATION, "token secret")
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[test]
+    fn redirect_to_other_host_drops_authorization() {
+        let mut request = request_with_token("https://api.github.com/repos/a/b");
+        follow_redirect(&mut request, "https://example.com/repos/a/b").unwrap();
+
+        assert_eq!(request.uri(), "https://example.com/repos/a/b");
+        assert!(request.headers().get(AUTHORIZATION).is_none());
+    }
+
+    #[test]
+    fn redirect_to_other_scheme_or_port_drops_authorization() {
+        let mut request = request_with_token("https://api.github.com/repos/a/b");
+        follow_redirect(&mut request, "http://api.github.com/repos/a/b").unwrap();
+        assert!(request.headers().get(AUTHORIZATION).is_none());
+
+        let mut request = request_with_token("https://api.github.com/repos/a/b");
+        follow_redirect(&mut request, "https://api.github.com:8443/repos/a/b").unwrap();
+        assert!(request.headers().get(AUTHORIZATION).is_none());
+    }
+
+    #[test]
+    fn redirect_to_same_origin_keeps_authorization() {
+        let mut request = request_with_token("https://api.github.com/repos/a/b");
+        follow_redirect(&mut request, "https://API.github.com:443/repositories/1").unwrap();
+
+        assert_eq!(request.uri(), "https://API.github.com:443/repositories/1");
+        assert!(request.headers().get(AUTHORIZATION).is_some());
+    }
+
+    #[test]
+    fn relative_redirects_resolve_against_current_uri() {
+        let base = "https://api.github.com/repos/a/b?page=1"
+            .parse::<Uri>()
+            .unwrap();
+
+        for (location, expected) in [
+            ("/repositories/1", "https://api.github.com/repositories/1"),
+            ("c", "https://api.github.com/repos/a/c"),
+            ("?page=2", "https://api.github.com/repos/a/b?page=2"),
+            ("//example.com/x", "https://example.com/x"),
+        ] {
+            assert_eq!(resolve_location(&base, location).unwrap(), expected);
+        }
+
+        let mut request = request_with_token("https://api.github.com/repos/a/b");
+        follow_redirect(&mut request, "/repositories/1").unwrap();
+        assert!(request.headers().get(AUTHORIZATION).is_some());
+    }
+}
@@ -0,0 +1,504 @@
+use crate::combinators::{self, Condition};
+use proc_macro2::{Delimiter, Span, TokenStream, TokenTree};
+use quote::ToTokens;
+use syn::{parse::Parse, spanned::Spanned};
+
+/// Expand `#[todo_or_die::when(...)]`, passing the item through unchanged along with the
+/// compile errors of any checks that fail. The errors point at the name of the item.
+///
+/// On structs, fields can also be annotated with `#[when(...)]`. Attribute macros can't be used
+/// on fields directly, so those attributes are removed here and their checks performed as well.
+pub(crate) fn when(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let (description, span) = describe(&item);
+    let description = format!("`{}`", description);
+    let (item, fields) = take_field_conditions(item, &description);
+
+    if attr.is_empty() && fields.is_empty() {
+        let mut tokens = quote::quote! {
+            ::std::compile_error!(
+                "expected a condition, such as `#[todo_or_die::when(after_date(2026, 1, 1))]`"
+            );
+        };
+        tokens.extend(item);
+        return tokens;
+    }
+
+    let mut tokens = TokenStream::new();
+    if !attr.is_empty() {
+        tokens.extend(check(attr, description, span));
+    }
+    for (attr, description, span) in fields {
+        tokens.extend(check(attr, description, span));
+    }
+    tokens.extend(item);
+    tokens
+}
+
+/// Perform the check in `attr`, with the compile error naming the item and pointing at `span`.
+pub(crate) fn check(attr: TokenStream, description: String, span: Span) -> TokenStream {
+    crate::perform_check_at(attr.into(), span, move |condition: Condition, _| {
+        combinators::on_item(condition, &description)
+    })
+    .into()
+}
+
+/// Expand `#[todo_or_die::deprecate_after(...)]`, marking the item as `#[deprecated]` once the
+/// condition holds and triggering a compile error once `error_after` does.
+pub(crate) fn deprecate_after(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let (description, span) = describe(&item);
+    let description = format!("`{}`", description);
+
+    let mut deprecated = None;
+    let mut tokens = TokenStream::from(crate::perform_check_at(
+        attr.into(),
+        span,
+        |input: DeprecateAfter, modifiers| {
+            if input.condition.evaluate()?.is_some() {
+                let note = input.note.map(|note| quote::quote! { note = #note });
+                let attr = match &modifiers.only {
+                    Some(predicate) => {
+                        quote::quote! { #[cfg_attr(#predicate, deprecated(#note))] }
+                    }
+                    None => quote::quote! { #[deprecated(#note)] },
+                };
+                deprecated = Some(attr);
+            }
+
+            match input.error_after {
+                Some(error_after) => combinators::on_item(error_after, &description),
+                None => Ok(None),
+            }
+        },
+    ));
+
+    tokens.extend(deprecated);
+    tokens.extend(item);
+    tokens
+}
+
+/// The arguments to `#[todo_or_die::deprecate_after(...)]`, such as
+/// `date(2025, 9, 1), note = "use new_api instead", error_after = date(2026, 1, 1)`.
+struct DeprecateAfter {
+    condition: Condition,
+    note: Option<syn::LitStr>,
+    error_after: Option<Condition>,
+}
+
+impl Parse for DeprecateAfter {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let condition = input.parse()?;
+        let mut note = None;
+        let mut error_after = None;
+
+        while input.parse::<syn::token::Comma>().is_ok() && !input.is_empty() {
+            let ident = input.parse::<syn::Ident>()?;
+            input.parse::<syn::token::Eq>()?;
+
+            let duplicate = || syn::Error::new(ident.span(), format!("duplicate `{}`", ident));
+
+            if ident == "note" {
+                if note.is_some() {
+                    return Err(duplicate());
+                }
+                note = Some(input.parse()?);
+            } else if ident == "error_after" {
+                if error_after.is_some() {
+                    return Err(duplicate());
+                }
+                error_after = Some(input.parse()?);
+            } else {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    "expected `note` or `error_after`",
+                ));
+            }
+        }
+
+        if !input.is_empty() {
+            return Err(input.error("unexpected argument"));
+        }
+
+        Ok(Self {
+            condition,
+            note,
+            error_after,
+        })
+    }
+}
+
+/// Expand `#[todo_or_die::remove_feature(...)]`, triggering a compile error if the feature is
+/// enabled once the condition holds.
+pub(crate) fn remove_feature(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let (_, span) = describe(&item);
+
+    let mut tokens = TokenStream::from(crate::perform_check_at(
+        attr.into(),
+        span,
+        |input: RemoveFeature, modifiers| {
+            // the macro can't tell which features the crate is compiled with so leave it to `cfg`
+            let feature = &input.feature;
+            modifiers.only = Some(match modifiers.only.take() {
+                Some(predicate) => syn::parse_quote! { all(feature = #feature, #predicate) },
+                None => syn::parse_quote! { feature = #feature },
+            });
+
+            let description = format!("the `{}` feature should be removed", feature.value());
+            combinators::on_item(input.after, &description)
+        },
+    ));
+
+    tokens.extend(item);
+    tokens
+}
+
+/// The arguments to `#[todo_or_die::remove_feature(...)]`, such as
+/// `"legacy-api", after = date(2026, 3, 1)`.
+struct RemoveFeature {
+    feature: syn::LitStr,
+    after: Condition,
+}
+
+impl Parse for RemoveFeature {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let feature = input.parse::<syn::LitStr>()?;
+        if feature.value().is_empty() {
+            return Err(syn::Error::new(
+                feature.span(),
+                "expected the name of a feature",
+            ));
+        }
+        input.parse::<syn::token::Comma>()?;
+
+        let ident = input.parse::<syn::Ident>()?;
+        if ident != "after" {
+            return Err(syn::Error::new(ident.span(), "expected `after`"));
+        }
+        input.parse::<syn::token::Eq>()?;
+        let after = input.parse()?;
+
+        input.parse::<syn::token::Comma>().ok();
+        if !input.is_empty() {
+            return Err(input.error("unexpected argument"));
+        }
+
+        Ok(Self { feature, after })
+    }
+}
+
+/// Remove `#[when(...)]` from the fields of a struct, returning the conditions along with a
+/// description and span of each field.
+fn take_field_conditions(
+    item: TokenStream,
+    description: &str,
+) -> (TokenStream, Vec<(TokenStream, String, Span)>) {
+    let mut input = match syn::parse2::<syn::DeriveInput>(item.clone()) {
+        Ok(input) => input,
+        Err(_) => return (item, Vec::new()),
+    };
+
+    let fields = match &mut input.data {
+        syn::Data::Struct(data) => &mut data.fields,
+        _ => return (item, Vec::new()),
+    };
+
+    let mut conditions = Vec::new();
+    for (idx, field) in fields.iter_mut().enumerate() {
+        let (name, span) = match &field.ident {
+            Some(ident) => (
+                format!("field `{}` of {}", ident, description),
+                ident.span(),
+            ),
+            None => (format!("field {} of {}", idx, description), field.ty.span()),
+        };
+
+        let mut attrs = Vec::new();
+        for attr in field.attrs.drain(..) {
+            if is_when(&attr.path) {
+                conditions.push((parenthesized(attr.tokens), name.clone(), span));
+            } else {
+                attrs.push(attr);
+            }
+        }
+        field.attrs = attrs;
+    }
+
+    if conditions.is_empty() {
+        return (item, conditions);
+    }
+
+    (input.into_token_stream(), conditions)
+}
+
+/// Whether the path is `when` or `todo_or_die::when`.
+fn is_when(path: &syn::Path) -> bool {
+    let segments = path
+        .segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect::<Vec<_>>();
+    segments == ["when"] || segments == ["todo_or_die", "when"]
+}
+
+/// The tokens inside the parentheses of `#[when(...)]`.
+fn parenthesized(tokens: TokenStream) -> TokenStream {
+    let mut tokens = tokens.into_iter();
+    match (tokens.next(), tokens.next()) {
+        (Some(TokenTree::Group(group)), None) if group.delimiter() == Delimiter::Parenthesis => {
+            group.stream()
+        }
+        _ => TokenStream::new(),
+    }
+}
+
+/// A short description of an item, such as `fn retry_shim` or `impl Display for Wrapper`, and
+/// the span of its name.
+pub(crate) fn describe(item: &TokenStream) -> (String, Span) {
+    let tokens = item.clone().into_iter().collect::<Vec<_>>();
+    let mut idx = 0;
+
+    // skip attributes, visibility, and qualifiers such as `async` or `extern "C"`
+    while idx < tokens.len() {
+        match &tokens[idx..] {
+            [TokenTree::Punct(punct), TokenTree::Group(_), ..] if punct.as_char() == '#' => {
+                idx += 2;
+            }
+            [TokenTree::Ident(ident), TokenTree::Group(group), ..]
+                if ident == "pub" && group.delimiter() == Delimiter::Parenthesis =>
+            {
+                idx += 2;
+            }
+            [TokenTree::Ident(ident), TokenTree::Literal(_), ..] if ident == "extern" => {
+                idx += 2;
+            }
+            [TokenTree::Ident(ident), TokenTree::Ident(next), ..]
+                if ident == "pub"
+                    || ident == "async"
+                    || ident == "unsafe"
+                    || ident == "default"
+                    || (ident == "const"
+                        && (next == "fn" || next == "unsafe" || next == "async"))
+                    || (ident == "extern" && next == "fn") =>
+            {
+                idx += 1;
+            }
+            _ => break,
+        }
+    }
+
+    match &tokens[idx..] {
+        [TokenTree::Ident(kind), ..] if kind == "impl" => {
+            let header = tokens[idx..]
+                .iter()
+                .take_while(|token| {
+                    !matches!(token, TokenTree::Group(group) if group.delimiter() == Delimiter::Brace)
+                        && !matches!(token, TokenTree::Ident(ident) if ident == "where")
+                })
+                .cloned()
+                .collect::<TokenStream>();
+            (render(header), kind.span())
+        }
+        [TokenTree::Ident(kind), TokenTree::Ident(name), ..] => {
+            (format!("{} {}", kind, name), name.span())
+        }
+        [TokenTree::Ident(kind), TokenTree::Punct(bang), TokenTree::Ident(name), ..]
+            if kind == "macro_rules" && bang.as_char() == '!' =>
+        {
+            (format!("macro_rules! {}", name), name.span())
+        }
+        _ => ("item".to_owned(), Span::call_site()),
+    }
+}
+
+/// Render tokens as they'd usually be written, such as `Wrapper<T>` rather than `Wrapper < T >`.
+fn render(tokens: TokenStream) -> String {
+    let mut rendered = tokens.to_string();
+    for (from, to) in [
+        (" <", "<"),
+        ("< ", "<"),
+        (" >", ">"),
+        (" ,", ","),
+        (" :: ", "::"),
+        (" : ", ": "),
+        ("& ", "&"),
+        ("' ", "'"),
+    ] {
+        rendered = rendered.replace(from, to);
+    }
+    rendered
+}
+
+/// ```compile_fail
+/// #[todo_or_die::when(after_date(1990, 01, 01))]
+/// fn retry_shim() {}
+/// ```
+///
+/// ```
+/// #[todo_or_die::when(after_date(3000, 01, 01))]
+/// fn retry_shim() {}
+///
+/// retry_shim();
+/// ```
+///
+/// on other items
+/// ```
+/// #[todo_or_die::when(after_date(3000, 01, 01))]
+/// mod legacy {
+///     pub(crate) fn f() {}
+/// }
+///
+/// struct Wrapper<T>(T);
+///
+/// #[todo_or_die::when(after_date(3000, 01, 01))]
+/// impl<T: std::fmt::Debug> std::fmt::Debug for Wrapper<T> {
+///     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+///         self.0.fmt(f)
+///     }
+/// }
+///
+/// #[todo_or_die::when(after_date(3000, 01, 01))]
+/// pub(crate) async unsafe fn shim() {}
+///
+/// legacy::f();
+/// ```
+///
+/// ```compile_fail
+/// #[todo_or_die::when(after_date(1990, 01, 01))]
+/// impl Clone for Config {
+///     fn clone(&self) -> Self { Config }
+/// }
+///
+/// struct Config;
+/// ```
+///
+/// on fields
+/// ```
+/// #[todo_or_die::when]
+/// #[derive(Default)]
+/// struct Config {
+///     #[when(after_date(3000, 01, 01))]
+///     retries: u32,
+///     #[todo_or_die::when(after_date(3000, 01, 01))]
+///     timeout: u32,
+/// }
+///
+/// let _ = Config::default().retries;
+/// ```
+///
+/// ```compile_fail
+/// #[todo_or_die::when]
+/// struct Config {
+///     #[when(after_date(1990, 01, 01))]
+///     retries: u32,
+/// }
+/// ```
+///
+/// ```compile_fail
+/// #[todo_or_die::when]
+/// struct Config(#[when(after_date(1990, 01, 01))] u32);
+/// ```
+///
+/// with modifiers
+/// ```
+/// #[todo_or_die::when(warn: after_date(1990, 01, 01), owner = "@alice")]
+/// fn retry_shim() {}
+/// ```
+///
+/// ```
+/// #[todo_or_die::when(after_date(1990, 01, 01), only(target_os = "no-such-os"))]
+/// fn retry_shim() {}
+/// ```
+///
+/// missing condition
+/// ```compile_fail
+/// #[todo_or_die::when]
+/// fn retry_shim() {}
+/// ```
+///
+/// ```compile_fail
+/// #[todo_or_die::when(no_such_check(1))]
+/// fn retry_shim() {}
+/// ```
+///
+/// # `deprecate_after`
+///
+/// ```compile_fail
+/// #![deny(deprecated)]
+/// #[todo_or_die::deprecate_after(date(1990, 01, 01), note = "use new_api instead")]
+/// fn old_api() {}
+///
+/// old_api();
+/// ```
+///
+/// ```
+/// #![deny(deprecated)]
+/// #[todo_or_die::deprecate_after(date(3000, 01, 01), note = "use new_api instead")]
+/// fn old_api() {}
+///
+/// old_api();
+/// ```
+///
+/// not used anywhere
+/// ```
+/// #[todo_or_die::deprecate_after(date(1990, 01, 01))]
+/// struct Old;
+/// ```
+///
+/// ```
+/// #![deny(deprecated)]
+/// #[todo_or_die::deprecate_after(date(1990, 01, 01), only(target_os = "no-such-os"))]
+/// fn old_api() {}
+///
+/// old_api();
+/// ```
+///
+/// `error_after`
+/// ```compile_fail
+/// #[todo_or_die::deprecate_after(date(1990, 01, 01), error_after = date(1991, 01, 01))]
+/// fn old_api() {}
+/// ```
+///
+/// ```
+/// #[todo_or_die::deprecate_after(
+///     date(1990, 01, 01),
+///     note = "use new_api instead",
+///     error_after = after_date(3000, 01, 01),
+/// )]
+/// fn old_api() {}
+/// ```
+///
+/// ```compile_fail
+/// #[todo_or_die::deprecate_after(date(3000, 01, 01), note = "a", note = "b")]
+/// fn old_api() {}
+/// ```
+///
+/// ```compile_fail
+/// #[todo_or_die::deprecate_after(date(3000, 01, 01), since = "1.0")]
+/// fn old_api() {}
+/// ```
+///
+/// # `remove_feature`
+///
+/// doctests are compiled without any features
+/// ```
+/// #[todo_or_die::remove_feature("legacy-api", after = date(1990, 01, 01))]
+/// fn legacy() {}
+/// ```
+///
+/// invalid arguments fail without the feature
+/// ```compile_fail
+/// #[todo_or_die::remove_feature("legacy-api", after = date(3000, 13, 01))]
+/// fn legacy() {}
+/// ```
+///
+/// ```compile_fail
+/// #[todo_or_die::remove_feature("legacy-api", date(3000, 01, 01))]
+/// fn legacy() {}
+/// ```
+///
+/// ```compile_fail
+/// #[todo_or_die::remove_feature("", after = date(3000, 01, 01))]
+/// fn legacy() {}
+/// ```
+#[cfg(feature = "time")]
+#[allow(dead_code)]
+fn tests() {}
@@ -1,19 +1,232 @@
-use crate::http::request;
+use crate::{
+    http::{request, request_text, NotFound},
+    threshold::Threshold,
+};
 use anyhow::{Context as _, Result};
-use hyper::Request;
+use chrono::{DateTime, Months, Utc};
+use hyper::{header::HeaderValue, header::AUTHORIZATION, Request};
 use semver::{Version, VersionReq};
 use serde::Deserialize;
 use syn::parse::Parse;
 
+const CRATES_IO_INDEX: &str = "https://index.crates.io";
+
 pub(crate) fn crates_io(input: Input) -> Result<Option<String>> {
+    let entries = index_entries(CRATES_IO_INDEX, &input.krate)?;
+    let latest_version = latest_version(&entries, input.filter)?;
+
+    if input.version_req.matches(&latest_version) {
+        Ok(Some(format!(
+            "Latest version of {} is {}. Time to act on this!",
+            input.krate, latest_version
+        )))
+    } else {
+        Ok(None)
+    }
+}
+
+pub(crate) fn registry(input: RegistryInput) -> Result<Option<String>> {
+    let RegistryInput {
+        krate,
+        version_req,
+        filter,
+        registry,
+    } = input;
+
+    let entries = index_entries(&registry, &krate)?;
+    let latest_version = latest_version(&entries, filter)?;
+
+    if version_req.matches(&latest_version) {
+        Ok(Some(format!(
+            "Latest version of {} on {} is {}. Time to act on this!",
+            krate, registry, latest_version
+        )))
+    } else {
+        Ok(None)
+    }
+}
+
+pub(crate) fn crate_yanked(input: RegistryInput) -> Result<Option<String>> {
+    let RegistryInput {
+        krate,
+        version_req,
+        registry,
+        ..
+    } = input;
+
+    let entries = index_entries(&registry, &krate)?;
+
+    let matching = entries
+        .iter()
+        .filter_map(|entry| Some((entry.vers.parse::<Version>().ok()?, entry.yanked)))
+        .filter(|(version, _)| version_req.matches(version))
+        .collect::<Vec<_>>();
+
+    if matching.is_empty() {
+        anyhow::bail!("No versions of {} match {}", krate, version_req);
+    }
+
+    let yanked = matching
+        .iter()
+        .filter(|(_, yanked)| *yanked)
+        .map(|(version, _)| version.to_string())
+        .collect::<Vec<_>>();
+
+    if let [version] = &*yanked {
+        Ok(Some(format!(
+            "{} {} has been yanked. Time to act on this!",
+            krate, version
+        )))
+    } else if !yanked.is_empty() {
+        Ok(Some(format!(
+            "{} {} have been yanked. Time to act on this!",
+            krate,
+            yanked.join(", ")
+        )))
+    } else {
+        Ok(None)
+    }
+}
+
+pub(crate) fn crate_published(input: CratePublished) -> Result<Option<String>> {
+    let entries = index_entries(CRATES_IO_INDEX, &input.krate)?;
+
+    let published = entries
+        .iter()
+        .filter(|entry| !entry.yanked)
+        .filter_map(|entry| entry.vers.parse::<Version>().ok())
+        .any(|version| version == input.version);
+
+    if published {
+        Ok(Some(format!(
+            "{} {} has been published. Time to act on this!",
+            input.krate, input.version
+        )))
+    } else {
+        Ok(None)
+    }
+}
+
+pub(crate) fn crate_msrv(input: Input) -> Result<Option<String>> {
+    let entries = index_entries(CRATES_IO_INDEX, &input.krate)?;
+    let (latest_version, entry) = latest_entry(&entries, input.filter)?;
+
+    let rust_version = match &entry.rust_version {
+        Some(rust_version) => rust_version,
+        None => return Ok(None),
+    };
+
+    let msrv = parse_rust_version(rust_version)
+        .with_context(|| format!("Couldn't parse rust-version `{}`", rust_version))?;
+
+    if input.version_req.matches(&msrv) {
+        Ok(Some(format!(
+            "Latest version of {} ({}) declares rust-version {}. Time to act on this!",
+            input.krate, latest_version, rust_version
+        )))
+    } else {
+        Ok(None)
+    }
+}
+
+pub(crate) fn crate_downloads(input: CrateDownloads) -> Result<Option<String>> {
+    #[derive(Debug, Deserialize)]
+    struct Response {
+        #[serde(rename = "crate")]
+        krate: Crate,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Crate {
+        downloads: u64,
+    }
+
+    // download counts aren't part of the index so this has to use the API
+    let data = request::<Response>(
+        Request::builder()
+            .uri(format!("https://crates.io/api/v1/crates/{}", input.krate))
+            .body(())
+            .unwrap(),
+    )?;
+
+    let downloads = data.krate.downloads;
+
+    if input.threshold.matches(downloads) {
+        Ok(Some(format!(
+            "{} has {} downloads on crates.io. Time to act on this!",
+            input.krate, downloads
+        )))
+    } else {
+        Ok(None)
+    }
+}
+
+pub(crate) fn crate_release_age(input: CrateReleaseAge) -> Result<Option<String>> {
     #[derive(Debug, Deserialize)]
     struct Response {
-        versions: Vec<CrateVersion>,
+        version: CrateVersion,
     }
 
     #[derive(Debug, Deserialize)]
     struct CrateVersion {
-        num: String,
+        created_at: DateTime<Utc>,
+    }
+
+    let entries = index_entries(CRATES_IO_INDEX, &input.krate)?;
+
+    let version = entries
+        .iter()
+        .filter(|entry| !entry.yanked)
+        .filter_map(|entry| entry.vers.parse::<Version>().ok())
+        .filter(|version| input.version_req.matches(version))
+        .max()
+        .with_context(|| format!("No versions of {} match {}", input.krate, input.version_req))?;
+
+    // publish dates aren't part of the index so this has to use the API
+    let data = request::<Response>(
+        Request::builder()
+            .uri(format!(
+                "https://crates.io/api/v1/crates/{}/{}",
+                input.krate, version
+            ))
+            .body(())
+            .unwrap(),
+    )?;
+
+    let published_at = data.version.created_at;
+    let deadline = published_at
+        .checked_add_months(Months::new(input.months))
+        .context("Date out of range")?;
+
+    if deadline <= crate::clock::now()? {
+        Ok(Some(format!(
+            "{} {} was published on {}, more than {} months ago. Time to act on this!",
+            input.krate,
+            version,
+            published_at.date_naive(),
+            input.months
+        )))
+    } else {
+        Ok(None)
+    }
+}
+
+#[cfg(feature = "github")]
+pub(crate) fn crate_repo_archived(input: CrateName) -> Result<Option<String>> {
+    #[derive(Debug, Deserialize)]
+    struct Response {
+        #[serde(rename = "crate")]
+        krate: Crate,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Crate {
+        repository: Option<String>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct GitLabProject {
+        archived: bool,
     }
 
     let data = request::<Response>(
@@ -23,46 +236,410 @@ pub(crate) fn crates_io(input: Input) -> Result<Option<String>> {
             .unwrap(),
     )?;
 
-    let latest_version = data
-        .versions
-        .first()
-        .context("No versions found for crate")?
-        .num
-        .parse::<Version>()?;
+    let repository = data
+        .krate
+        .repository
+        .with_context(|| format!("{} doesn't specify a repository", input.krate))?;
 
-    if input.version_req.matches(&latest_version) {
+    let (host, path) = split_repository_url(&repository)
+        .with_context(|| format!("Unsupported repository URL `{}`", repository))?;
+
+    let archived = match host {
+        "github.com" => {
+            let mut segments = path.split('/');
+            match (segments.next(), segments.next()) {
+                (Some(org), Some(repo)) => crate::github::repo_archived(org, repo)?,
+                _ => anyhow::bail!("Unsupported repository URL `{}`", repository),
+            }
+        }
+        "gitlab.com" => {
+            request::<GitLabProject>(
+                Request::builder()
+                    .uri(format!(
+                        "https://gitlab.com/api/v4/projects/{}",
+                        path.replace('/', "%2F")
+                    ))
+                    .extension(crate::http::NotFoundIsntFatal)
+                    .body(())
+                    .unwrap(),
+            )?
+            .archived
+        }
+        _ => anyhow::bail!(
+            "Unsupported repository host `{}`. Only GitHub and GitLab are supported",
+            host
+        ),
+    };
+
+    if archived {
         Ok(Some(format!(
-            "Latest version of {} is {}. Time to act on this!",
-            input.krate, latest_version
+            "The repository of {} ({}) has been archived. Time to act on this!",
+            input.krate, repository
         )))
     } else {
         Ok(None)
     }
 }
 
+/// Split a repository URL like `https://github.com/serde-rs/serde.git` into its host and path,
+/// such as `("github.com", "serde-rs/serde")`.
+#[cfg(feature = "github")]
+fn split_repository_url(url: &str) -> Option<(&str, &str)> {
+    let url = url
+        .trim()
+        .trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .trim_start_matches("www.");
+    let url = url.split(&['?', '#'][..]).next()?;
+    let (host, path) = url.split_once('/')?;
+
+    // links into a repository, such as `/tree/master/serde_derive`, don't change which
+    // repository it is
+    let path = path.split("/-/").next()?;
+    let path = path.split("/tree/").next()?;
+    let path = path.trim_end_matches('/').trim_end_matches(".git");
+
+    if path.is_empty() {
+        None
+    } else {
+        Some((host, path))
+    }
+}
+
+pub(crate) fn crate_exists(input: CrateName) -> Result<Option<String>> {
+    let entries = match index_entries(CRATES_IO_INDEX, &input.krate) {
+        Ok(entries) => entries,
+        Err(err) if err.is::<NotFound>() => return Ok(None),
+        Err(err) => return Err(err),
+    };
+
+    if entries.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(format!(
+            "{} has been published on crates.io. Time to act on this!",
+            input.krate
+        )))
+    }
+}
+
+/// The latest version of a crate on crates.io, the same way `crates_io!` determines it.
+#[allow(dead_code)]
+pub(crate) fn latest_crates_io_version(krate: &str) -> Result<Version> {
+    let entries = index_entries(CRATES_IO_INDEX, krate)?;
+    latest_version(&entries, VersionFilter::default())
+}
+
+/// A line in a crate's file in a registry index. Each line describes one published version.
+#[derive(Debug, Deserialize)]
+struct IndexEntry {
+    vers: String,
+    yanked: bool,
+    #[serde(default)]
+    rust_version: Option<String>,
+}
+
+/// Fetch every version of a crate from a [sparse index].
+///
+/// This is used rather than the crates.io API since index files are much smaller and aren't
+/// subject to the API's crawler policy.
+///
+/// [sparse index]: https://doc.rust-lang.org/cargo/reference/registry-index.html#sparse-protocol
+fn index_entries(registry: &str, krate: &str) -> Result<Vec<IndexEntry>> {
+    let mut request = Request::builder()
+        .uri(format!("{}/{}", registry, index_path(krate)))
+        .body(())
+        .unwrap();
+
+    if registry != CRATES_IO_INDEX {
+        if let Ok(token) = std::env::var("TODO_OR_DIE_CARGO_REGISTRY_TOKEN") {
+            request.headers_mut().insert(
+                AUTHORIZATION,
+                HeaderValue::from_str(&token)
+                    .context("Cargo registry token contained invalid header value")?,
+            );
+        }
+    }
+
+    let text = request_text(request)?;
+
+    text.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(serde_json::from_str::<IndexEntry>)
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to parse index entry")
+}
+
+/// The greatest version that isn't yanked or a pre-release, unless `filter` says to include those.
+fn latest_version(entries: &[IndexEntry], filter: VersionFilter) -> Result<Version> {
+    latest_entry(entries, filter).map(|(version, _)| version)
+}
+
+/// Like [`latest_version`] but also returns the index entry for the version.
+fn latest_entry(entries: &[IndexEntry], filter: VersionFilter) -> Result<(Version, &IndexEntry)> {
+    entries
+        .iter()
+        .filter(|entry| filter.include_yanked || !entry.yanked)
+        .filter_map(|entry| Some((entry.vers.parse::<Version>().ok()?, entry)))
+        .filter(|(version, _)| filter.include_prereleases || version.pre.is_empty())
+        .max_by(|(a, _), (b, _)| a.cmp(b))
+        .with_context(|| match (filter.include_prereleases, filter.include_yanked) {
+            (true, true) => "No versions found for crate",
+            (true, false) => "No versions found for crate. Use `include_yanked` to consider yanked versions",
+            (false, true) => "No versions found for crate. Use `include_prereleases` to consider pre-releases",
+            (false, false) => "No versions found for crate. Use `include_prereleases` or `include_yanked` to consider more versions",
+        })
+}
+
+/// Parse a `rust-version` such as `1.80` or `1.80.1` into a [`Version`].
+fn parse_rust_version(rust_version: &str) -> Option<Version> {
+    let mut components = rust_version.trim().split('.');
+    let major = components.next()?.parse().ok()?;
+    let minor = components.next().unwrap_or("0").parse().ok()?;
+    let patch = components.next().unwrap_or("0").parse().ok()?;
+    if components.next().is_some() {
+        return None;
+    }
+    Some(Version::new(major, minor, patch))
+}
+
+/// The path of a crate's file in a registry index, such as `se/rd/serde`.
+///
+/// See <https://doc.rust-lang.org/cargo/reference/registry-index.html#index-files>.
+fn index_path(krate: &str) -> String {
+    let krate = krate.to_lowercase();
+    match krate.len() {
+        1 => format!("1/{}", krate),
+        2 => format!("2/{}", krate),
+        3 => format!("3/{}/{}", &krate[..1], krate),
+        _ => format!("{}/{}/{}", &krate[..2], &krate[2..4], krate),
+    }
+}
+
 pub(crate) struct Input {
     krate: String,
     version_req: VersionReq,
+    filter: VersionFilter,
 }
 
 impl Parse for Input {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        let krate = input.parse::<syn::LitStr>()?.value();
+        let (krate, version_req) = parse_krate_and_version_req(input)?;
+
+        let mut filter = VersionFilter::default();
+        while !input.is_empty() {
+            let ident = input.parse::<syn::Ident>()?;
+            if !filter.set(&ident) {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    "expected `include_prereleases` or `include_yanked`",
+                ));
+            }
+
+            input.parse::<syn::token::Comma>().ok();
+        }
+
+        Ok(Self {
+            krate,
+            version_req,
+            filter,
+        })
+    }
+}
+
+/// Like [`Input`] but with an optional `registry = "..."` argument pointing at a sparse index.
+pub(crate) struct RegistryInput {
+    krate: String,
+    version_req: VersionReq,
+    filter: VersionFilter,
+    registry: String,
+}
+
+impl Parse for RegistryInput {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let (krate, version_req) = parse_krate_and_version_req(input)?;
+
+        let mut filter = VersionFilter::default();
+        let mut registry = CRATES_IO_INDEX.to_owned();
+        while !input.is_empty() {
+            let ident = input.parse::<syn::Ident>()?;
+            if ident == "registry" {
+                input.parse::<syn::token::Eq>()?;
+
+                let lit = input.parse::<syn::LitStr>()?;
+                let value = lit.value();
+                let value = value.trim_start_matches("sparse+").trim_end_matches('/');
+                if !value.starts_with("https://") && !value.starts_with("http://") {
+                    return Err(syn::Error::new(
+                        lit.span(),
+                        "expected the URL of a sparse registry index",
+                    ));
+                }
+                registry = value.to_owned();
+            } else if !filter.set(&ident) {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    "expected `registry`, `include_prereleases`, or `include_yanked`",
+                ));
+            }
+
+            input.parse::<syn::token::Comma>().ok();
+        }
+
+        Ok(Self {
+            krate,
+            version_req,
+            filter,
+            registry,
+        })
+    }
+}
+
+fn parse_krate_and_version_req(
+    input: syn::parse::ParseStream,
+) -> syn::Result<(String, VersionReq)> {
+    let krate = parse_krate_name(input)?;
+
+    input.parse::<syn::token::Comma>()?;
+
+    let lit = input.parse::<syn::LitStr>()?;
+    let version_req = lit
+        .value()
+        .parse()
+        .map_err(|err| syn::Error::new(lit.span(), err))?;
+
+    input.parse::<syn::token::Comma>().ok();
+
+    Ok((krate, version_req))
+}
+
+/// Parse a crate name, which must be ASCII letters, numbers, `-`, and `_`.
+fn parse_krate_name(input: syn::parse::ParseStream) -> syn::Result<String> {
+    let lit = input.parse::<syn::LitStr>()?;
+    let krate = lit.value();
+    if krate.is_empty() {
+        return Err(syn::Error::new(lit.span(), "crate name cannot be empty"));
+    }
+    if !krate
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(syn::Error::new(
+            lit.span(),
+            "crate names can only contain ASCII letters, numbers, `-`, and `_`",
+        ));
+    }
+    Ok(krate)
+}
+
+/// Which versions are considered when finding the latest version of a crate.
+#[derive(Default, Clone, Copy)]
+struct VersionFilter {
+    include_prereleases: bool,
+    include_yanked: bool,
+}
+
+impl VersionFilter {
+    /// Enable the flag named by `ident`. Returns `false` if there is no such flag.
+    fn set(&mut self, ident: &syn::Ident) -> bool {
+        if ident == "include_prereleases" {
+            self.include_prereleases = true;
+        } else if ident == "include_yanked" {
+            self.include_yanked = true;
+        } else {
+            return false;
+        }
+        true
+    }
+}
+
+/// A crate and an exact version, such as `"serde", "2.0.0"`.
+pub(crate) struct CratePublished {
+    krate: String,
+    version: Version,
+}
+
+impl Parse for CratePublished {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let krate = parse_krate_name(input)?;
 
         input.parse::<syn::token::Comma>()?;
 
         let lit = input.parse::<syn::LitStr>()?;
-        let version_req = lit
+        let version = lit
             .value()
+            .trim()
             .parse()
             .map_err(|err| syn::Error::new(lit.span(), err))?;
 
         input.parse::<syn::token::Comma>().ok();
 
-        Ok(Self { krate, version_req })
+        Ok(Self { krate, version })
     }
 }
 
+pub(crate) struct CrateDownloads {
+    krate: String,
+    threshold: Threshold,
+}
+
+impl Parse for CrateDownloads {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let krate = parse_krate_name(input)?;
+
+        input.parse::<syn::token::Comma>()?;
+
+        let threshold = input.parse::<Threshold>()?;
+
+        input.parse::<syn::token::Comma>().ok();
+
+        Ok(Self { krate, threshold })
+    }
+}
+
+pub(crate) struct CrateReleaseAge {
+    krate: String,
+    version_req: VersionReq,
+    months: u32,
+}
+
+impl Parse for CrateReleaseAge {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let (krate, version_req) = parse_krate_and_version_req(input)?;
+
+        let ident = input.parse::<syn::Ident>()?;
+        if ident != "months" {
+            return Err(syn::Error::new(ident.span(), "expected `months`"));
+        }
+        input.parse::<syn::token::Eq>()?;
+        let months = input.parse::<syn::LitInt>()?.base10_parse()?;
+
+        input.parse::<syn::token::Comma>().ok();
+
+        Ok(Self {
+            krate,
+            version_req,
+            months,
+        })
+    }
+}
+
+pub(crate) struct CrateName {
+    krate: String,
+}
+
+impl Parse for CrateName {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let krate = parse_krate_name(input)?;
+
+        input.parse::<syn::token::Comma>().ok();
+
+        Ok(Self { krate })
+    }
+}
+
+/// # `crates_io`
+///
 /// ```compile_fail
 /// todo_or_die::crates_io!("tokio", ">=1.0");
 /// ```
@@ -70,5 +647,140 @@ impl Parse for Input {
 /// ```
 /// todo_or_die::crates_io!("tokio", ">=10.0");
 /// ```
+///
+/// including pre-releases and yanked versions
+/// ```compile_fail
+/// todo_or_die::crates_io!("tokio", ">=1.0", include_prereleases, include_yanked);
+/// ```
+///
+/// invalid crate name
+/// ```compile_fail
+/// todo_or_die::crates_io!("éa", ">=0");
+/// ```
+///
+/// unknown flag
+/// ```compile_fail
+/// todo_or_die::crates_io!("tokio", ">=10.0", include_everything);
+/// ```
+///
+/// # `registry`
+///
+/// defaults to crates.io
+/// ```compile_fail
+/// todo_or_die::registry!("tokio", ">=1.0");
+/// ```
+///
+/// explicit registry
+/// ```compile_fail
+/// todo_or_die::registry!("tokio", ">=1.0", registry = "sparse+https://index.crates.io/");
+/// ```
+///
+/// ```
+/// todo_or_die::registry!("tokio", ">=10.0", registry = "https://index.crates.io");
+/// ```
+///
+/// short crate name
+/// ```compile_fail
+/// todo_or_die::registry!("syn", ">=1.0");
+/// ```
+///
+/// not a URL
+/// ```compile_fail
+/// todo_or_die::registry!("tokio", ">=10.0", registry = "my-registry");
+/// ```
+///
+/// # `crate_yanked`
+///
+/// yanked version
+/// ```compile_fail
+/// todo_or_die::crate_yanked!("tokio", "=1.0.0");
+/// ```
+///
+/// version that isn't yanked
+/// ```
+/// todo_or_die::crate_yanked!("tokio", "=1.38.0");
+/// ```
+///
+/// # `crate_published`
+///
+/// published version
+/// ```compile_fail
+/// todo_or_die::crate_published!("tokio", "1.0.0");
+/// ```
+///
+/// unpublished version
+/// ```
+/// todo_or_die::crate_published!("tokio", "1000.0.0");
+/// ```
+///
+/// version requirement rather than a version
+/// ```compile_fail
+/// todo_or_die::crate_published!("tokio", ">=1000.0.0");
+/// ```
+///
+/// # `crate_msrv`
+///
+/// declared MSRV matches
+/// ```compile_fail
+/// todo_or_die::crate_msrv!("tokio", ">=1.56");
+/// ```
+///
+/// declared MSRV doesn't match
+/// ```
+/// todo_or_die::crate_msrv!("tokio", ">=1.1000");
+/// ```
+///
+/// # `crate_downloads`
+///
+/// popular crate
+/// ```compile_fail
+/// todo_or_die::crate_downloads!("tokio", ">= 100000");
+/// ```
+///
+/// not popular enough yet
+/// ```
+/// todo_or_die::crate_downloads!("tokio", ">= 100_000_000_000_000");
+/// ```
+///
+/// # `crate_release_age`
+///
+/// old release
+/// ```compile_fail
+/// todo_or_die::crate_release_age!("tokio", "=1.0.0", months = 6);
+/// ```
+///
+/// not old enough yet
+/// ```
+/// todo_or_die::crate_release_age!("tokio", "=1.0.0", months = 100000);
+/// ```
+///
+/// missing `months`
+/// ```compile_fail
+/// todo_or_die::crate_release_age!("tokio", "=1.0.0");
+/// ```
+///
+/// # `crate_repo_archived`
+///
+/// crate whose repository has been archived
+/// ```compile_fail
+/// todo_or_die::crate_repo_archived!("failure");
+/// ```
+///
+/// crate whose repository is still active
+/// ```
+/// todo_or_die::crate_repo_archived!("tokio");
+/// ```
+///
+/// # `crate_exists`
+///
+/// published crate
+/// ```compile_fail
+/// todo_or_die::crate_exists!("tokio");
+/// ```
+///
+/// crate that hasn't been published
+/// ```
+/// todo_or_die::crate_exists!("todo-or-die-this-crate-does-not-exist");
+/// ```
 #[allow(dead_code)]
 fn tests() {}
@@ -7,12 +7,15 @@
 //! # Examples
 //!
 //! ```
+//! # #[cfg(feature = "time")]
 //! // trigger a compile error if we're past a certain date
 //! todo_or_die::after_date!(3000, 1, 1); // its the year 3000!
 //!
+//! # #[cfg(feature = "github")]
 //! // or a GitHub issue has closed
 //! todo_or_die::issue_closed!("rust-lang", "rust", 44265); // GATs are here!
 //!
+//! # #[cfg(feature = "crate")]
 //! // or the latest version of a crate matches some expression
 //! todo_or_die::crates_io!("serde", ">1.0.9000"); // its over 9000!
 //! ```
@@ -21,25 +24,206 @@
 //!
 //! The following optional features are available:
 //!
+//! - `archlinux`: Enables checking versions of packages on Arch Linux and the AUR.
 //! - `crate`: Enables checking versions of crates.
-//! - `github`: Enables checking if issues or pull requests are closed.
-//! - `rust`: Enables checking the current rust version.
+//! - `docker`: Enables checking tags of images on Docker Hub.
+//! - `endoflife`: Enables checking when products reach end-of-life according to
+//!   [endoflife.date].
+//! - `git`: Enables checking tags of git repositories and the history of your own code.
+//! - `github`: Enables checking things on GitHub, such as if issues or pull requests are closed.
+//! - `go`: Enables checking versions of Go modules.
+//! - `helm`: Enables checking versions of charts in Helm chart repositories.
+//! - `hex-pm`: Enables checking versions of packages on Hex.
+//! - `holidays`: Enables looking up holidays by country or URL for date macros. Implies `time`.
+//! - `lockfile`: Enables checking the versions of dependencies in `Cargo.lock`.
+//! - `maven`: Enables checking versions of artifacts on Maven Central.
+//! - `nixpkgs`: Enables checking versions of packages in nixpkgs.
+//! - `nightly-diagnostics`: Emits compile errors as proper diagnostics, with help and notes,
+//!   and reports problems such as failed requests as warnings rather than printing them, when
+//!   compiling with a nightly compiler. Does nothing on stable.
+//! - `npm`: Enables checking versions of packages on npm.
+//! - `nuget`: Enables checking versions of packages on NuGet.
+//! - `packagist`: Enables checking versions of packages on Packagist.
+//! - `pypi`: Enables checking versions of packages on PyPI.
+//! - `rubygems`: Enables checking versions of gems on RubyGems.
+//! - `rust`: Enables checking the current rust version and toolchain. Doesn't make network
+//!   requests.
+//! - `rust-upstream`: Enables checking upstream rust releases and the status of nightly features.
+//!   Implies `rust`.
+//! - `rustsec`: Enables checking for advisories in the RustSec advisory database.
+//! - `terraform`: Enables checking versions of providers and modules on the Terraform Registry.
 //! - `time`: Enables checking things to do with time.
+//! - `tls`: Enables checking when TLS certificates expire.
 //!
 //! Note that _none_ of the features are enabled by default.
 //!
 //! # Skipping checks
 //!
 //! If the environment variable `TODO_OR_DIE_SKIP` is set all macros will do
-//! nothing and immediately succeed. This can for example be used to skip checks
-//! locally and only perform them on CI.
+//! nothing and immediately succeed.
+//!
+//! Individual checks can be skipped by giving them an `id` (see [Identifying
+//! checks](#identifying-checks)) and listing it in `TODO_OR_DIE_SKIP_IDS`, for
+//! example `TODO_OR_DIE_SKIP_IDS=retry-shim,flaky-upstream`.
+//!
+//! A check that has already fired can instead be snoozed until a date with
+//! `TODO_OR_DIE_SNOOZE`, for example
+//! `TODO_OR_DIE_SNOOZE="retry-shim=2025-12-01,flaky-upstream=2026-01-15"`. The
+//! check is skipped until that date and enforced again afterwards. Snoozes can
+//! also be committed to the crate's `Cargo.toml`, with the environment variable
+//! taking precedence:
+//!
+//! ```toml
+//! [package.metadata.todo-or-die.snooze]
+//! retry-shim = "2025-12-01"
+//! ```
+//!
+//! Snoozing requires the `time` feature or one of the features that make network
+//! requests.
+//!
+//! If `TODO_OR_DIE_ONLY_CI` is set checks are only performed when running on
+//! CI and skipped otherwise. CI is detected through the environment variables
+//! common CI providers set, such as `CI` and `GITHUB_ACTIONS`. Variables set to
+//! an empty string, `0`, or `false` count as not being set. Setting it in
+//! `.cargo/config.toml` keeps local builds fast and offline:
+//!
+//! ```toml
+//! [env]
+//! TODO_OR_DIE_ONLY_CI = "1"
+//! ```
+//!
+//! Checks are also skipped when building documentation on [docs.rs], which
+//! doesn't allow network access, so the macros never break your documentation.
+//!
+//! Similarly checks that make network requests are skipped when building
+//! somewhere without network access, unless a cached response is available.
+//! That's detected by `CARGO_NET_OFFLINE` being set, or by building in the Nix
+//! sandbox. Checks that don't need the network, such as [`after_date!`], are
+//! still performed.
+//!
+//! # Saying what to do
+//!
+//! All macros accept a final `message = "..."` argument which is added to the compile error, so
+//! whoever sees it knows what to actually do:
+//!
+//! ```
+//! # #[cfg(feature = "time")]
+//! todo_or_die::after_date!(3000, 1, 1, message = "remove the retry shim in src/net.rs");
+//! ```
+//!
+//! Similarly `owner = "..."` is added to the compile error, so it's clear who to ask when a
+//! years old check fails, and `link = "..."` for pointing to a ticket or similar:
+//!
+//! ```
+//! # #[cfg(feature = "time")]
+//! todo_or_die::after_date!(3000, 1, 1, owner = "@alice", link = "JIRA-42");
+//! ```
+//!
+//! With many arguments [`check!`] can be easier to read.
+//!
+//! # Warning instead of failing
+//!
+//! Starting the arguments with `warn:` makes the macro emit a warning rather than a compile
+//! error, for getting some visibility before enforcing a check:
+//!
+//! ```
+//! # #[cfg(feature = "time")]
+//! todo_or_die::after_date!(warn: 1994, 10, 22);
+//! ```
+//!
+//! This works with all macros, such as `issue_closed!(warn: "org/repo#1")`. The warning is a
+//! regular rustc warning pointing at the macro call, so it shows up alongside your other warnings
+//! and is turned into an error by `-D warnings`.
+//!
+//! # Identifying checks
+//!
+//! All macros accept a final `id = "..."` argument with a stable identifier for the check. The
+//! `id` is included in the compile error and used to refer to the check elsewhere, such as when
+//! skipping it. It's also used to keep track of [grace periods](#grace-periods), so they can be
+//! shared with CI and don't restart when the macro's arguments change.
+//!
+//! ```
+//! # #[cfg(feature = "time")]
+//! todo_or_die::after_date!(3000, 1, 1, id = "retry-shim");
+//! ```
+//!
+//! Ids may only contain letters, numbers, `-`, and `_`.
+//!
+//! # Only enforcing checks on some targets
+//!
+//! All macros accept a final `only(...)` argument containing a `cfg` predicate. The compile error
+//! is then only triggered when compiling for targets matching the predicate:
+//!
+//! ```
+//! # #[cfg(feature = "time")]
+//! // our workaround is only needed on Windows so don't break other builds
+//! todo_or_die::after_date!(3000, 1, 1, only(target_os = "windows"));
+//! ```
+//!
+//! Note that the check itself, including any network requests, is still performed for all
+//! targets.
+//!
+//! Similarly `only_release` and `only_debug` only trigger the compile error in release and debug
+//! builds respectively:
+//!
+//! ```
+//! # #[cfg(feature = "time")]
+//! todo_or_die::after_date!(3000, 1, 1, only_release);
+//! ```
+//!
+//! Procedural macros can't see which profile is being built so by default the check is still
+//! performed and the error is made conditional on `debug_assertions`. To skip the check entirely,
+//! and avoid network requests in debug builds, forward the profile from a build script:
+//!
+//! ```no_run
+//! // in `build.rs`
+//! println!(
+//!     "cargo:rustc-env=TODO_OR_DIE_PROFILE={}",
+//!     std::env::var("PROFILE").unwrap(),
+//! );
+//! ```
+//!
+//! # Grace periods
+//!
+//! Macros that make network requests accept a final `grace_days = N` argument. When such a check
+//! first fails, for example because an issue was closed or a new version released, the time is
+//! recorded and for the next `N` days a warning is emitted instead of a compile error:
+//!
+//! ```ignore
+//! // don't block whoever is on call when a release happens at 2am
+//! todo_or_die::crates_io!("serde", ">=2", grace_days = 14);
+//! ```
+//!
+//! For checks with an [`id`](#identifying-checks) the time is recorded in a `.todo-or-die` file
+//! in the root of your crate. Commit that file so the grace period is shared by everyone,
+//! including CI machines that start from a fresh checkout:
+//!
+//! ```ignore
+//! todo_or_die::crates_io!("serde", ">=2", grace_days = 14, id = "serde-2");
+//! ```
+//!
+//! Without an `id` the time is recorded in a temporary directory, so each machine gets its own
+//! grace period and ephemeral CI machines always see the first failure. Once the check passes
+//! again the record is removed.
+//!
+//! # Pinning the current time
+//!
+//! Checks that depend on the current time, such as [`after_date!`], use the
+//! `TODO_OR_DIE_NOW` environment variable, an RFC 3339 timestamp like
+//! `2025-06-01T12:00:00Z`, as the current time if it's set. Otherwise
+//! `SOURCE_DATE_EPOCH` is used, if set, for compatibility with reproducible
+//! builds. This also applies to the expiry of cached HTTP responses.
+//!
+//! Dates are in UTC, unless `TODO_OR_DIE_TZ` is set to a timezone such as
+//! `Europe/Stockholm`. That includes the end-of-life dates checked by [`eol!`]
+//! and the expiry dates reported by [`cert_expires!`].
 //!
 //! # Caching HTTP requests
 //!
 //! By default HTTP requests will be cached. The behavior can be customized with
 //! these environment variables:
 //! - `TODO_OR_DIE_HTTP_CACHE_TTL_SECONDS`: How long cached responses will be
-//! used. The default is 1 hour.
+//!   used. The default is 1 hour.
 //! - `TODO_OR_DIE_DISABLE_HTTP_CACHE`: Disables caching if its set.
 //! - `TODO_OR_DIE_CLEAR_HTTP_CACHE`: Clears the cache if its set.
 //!
@@ -51,13 +235,26 @@
 //! some kind of error a warning will be printed but they wont trigger a compile
 //! error.
 //!
+//! The exception is if the server responds with `404 Not Found` or `410 Gone`.
+//! That means the issue, crate, etc. doesn't exist which is most likely a typo
+//! in the macro arguments, so that does trigger a compile error. The same goes
+//! for registries that say a package doesn't exist in some other way, such as
+//! with an empty search result. GitHub also responds with `404 Not Found` for
+//! private repositories, so without `TODO_OR_DIE_GITHUB_TOKEN` or
+//! `GITHUB_TOKEN` set that's only a compile error if the repository itself can
+//! be found.
+//! Lookups the macros do on their own, such as fetching files from rust-lang's
+//! repositories, never fail the build that way.
+//!
 //! [ruby]: https://rubygems.org/gems/todo_or_die
+//! [docs.rs]: https://docs.rs
+//! [endoflife.date]: https://endoflife.date
 
 #![warn(
     clippy::all,
     clippy::dbg_macro,
     clippy::todo,
-    clippy::empty_enum,
+    clippy::empty_enums,
     clippy::enum_glob_use,
     clippy::mem_forget,
     clippy::unused_self,
@@ -66,9 +263,7 @@
     clippy::needless_borrow,
     clippy::match_wildcard_for_single_variants,
     clippy::if_let_mutex,
-    clippy::mismatched_target_os,
     clippy::await_holding_lock,
-    clippy::match_on_vec_items,
     clippy::imprecise_flops,
     clippy::suboptimal_flops,
     clippy::lossy_float_literal,
@@ -87,27 +282,125 @@
     missing_debug_implementations,
     missing_docs
 )]
-#![deny(unreachable_pub, private_in_public)]
+#![deny(unreachable_pub)]
 #![allow(elided_lifetimes_in_paths, clippy::type_complexity)]
 #![forbid(unsafe_code)]
 #![cfg_attr(docsrs, feature(doc_cfg))]
+#![cfg_attr(todo_or_die_nightly_diagnostics, feature(proc_macro_diagnostic))]
 #![cfg_attr(test, allow(clippy::float_cmp))]
 
+mod modifiers;
+
+mod spec;
+
+mod block;
+mod combinators;
+mod item;
+
 #[cfg(feature = "__internal_http")]
 mod http;
 
+#[cfg(feature = "__internal_http")]
+mod grace;
+
+#[cfg(any(
+    feature = "time",
+    feature = "rust",
+    feature = "lockfile",
+    feature = "__internal_http"
+))]
+mod clock;
+
 #[cfg(feature = "github")]
 mod github;
 
 #[cfg(feature = "time")]
 mod time;
 
+#[cfg(feature = "time")]
+mod holidays;
+
+#[cfg(any(feature = "time", feature = "__internal_http"))]
+mod config;
+
+#[cfg(any(feature = "time", feature = "__internal_http"))]
+mod marker;
+
+#[cfg(any(feature = "time", feature = "__internal_http"))]
+mod snooze;
+
+#[cfg(todo_or_die_nightly_diagnostics)]
+mod diagnostic;
+
 #[cfg(feature = "crate")]
 mod krate;
 
 #[cfg(feature = "rust")]
 mod rust;
 
+#[cfg(feature = "rust-upstream")]
+mod rust_upstream;
+
+#[cfg(feature = "__internal_package")]
+mod package;
+
+#[cfg(any(feature = "github", feature = "crate"))]
+mod threshold;
+
+#[cfg(feature = "npm")]
+mod npm;
+
+#[cfg(feature = "pypi")]
+mod pypi;
+
+#[cfg(feature = "rubygems")]
+mod rubygems;
+
+#[cfg(feature = "docker")]
+mod docker;
+
+#[cfg(feature = "maven")]
+mod maven;
+
+#[cfg(feature = "nuget")]
+mod nuget;
+
+#[cfg(feature = "hex-pm")]
+mod hex_pm;
+
+#[cfg(feature = "packagist")]
+mod packagist;
+
+#[cfg(feature = "go")]
+mod go;
+
+#[cfg(feature = "nixpkgs")]
+mod nixpkgs;
+
+#[cfg(feature = "archlinux")]
+mod archlinux;
+
+#[cfg(feature = "terraform")]
+mod terraform;
+
+#[cfg(feature = "helm")]
+mod helm;
+
+#[cfg(feature = "rustsec")]
+mod rustsec;
+
+#[cfg(feature = "lockfile")]
+mod lockfile;
+
+#[cfg(feature = "git")]
+mod git;
+
+#[cfg(feature = "tls")]
+mod tls;
+
+#[cfg(feature = "endoflife")]
+mod endoflife;
+
 /// Trigger a compile error if an issue has been closed.
 ///
 /// Note that this will make network requests during compile which may make your builds flaky at
@@ -119,6 +412,17 @@ mod rust;
 ///
 /// ```compile_fail
 /// todo_or_die::issue_closed!("tokio-rs", "axum", 1);
+/// // todo_or_die::issue_closed!("tokio-rs/axum#1");
+/// // todo_or_die::issue_closed!("https://github.com/tokio-rs/axum/issues/1");
+/// ```
+///
+/// # Multiple issues
+///
+/// Several issues can be checked at once by prefixing them with `any:` or `all:`. `any:` triggers
+/// a compile error if any of the issues are closed and `all:` only if all of them are.
+///
+/// ```compile_fail
+/// todo_or_die::issue_closed!(any: "tokio-rs/axum#1", "rust-lang/rust#1563");
 /// ```
 ///
 /// # Authentication
@@ -133,6 +437,32 @@ pub fn issue_closed(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     perform_check(input, github::issue_closed)
 }
 
+/// Trigger a compile error if an issue has been closed or a date has passed, whichever happens
+/// first.
+///
+/// This is the same as [`issue_closed!`] combined with a deadline, so the TODO is dealt with even
+/// if the issue stays open. The date supports the same arguments as [`after_date!`].
+///
+/// Note that this will make network requests during compile which may make your builds flaky at
+/// times.
+///
+/// Requires the `github` and `time` features to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::issue_or_date!("rust-lang/rust#44265", 1994, 10, 22);
+/// ```
+///
+/// # Authentication
+///
+/// `issue_or_date` uses the same authentication as [`issue_closed!`].
+#[cfg(all(feature = "github", feature = "time"))]
+#[proc_macro]
+pub fn issue_or_date(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, github::issue_or_date)
+}
+
 /// Trigger a compile error if a pull request has been closed or merged.
 ///
 /// Note that this will make network requests during compile which may make your builds flaky at
@@ -145,6 +475,7 @@ pub fn issue_closed(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
 /// ```compile_fail
 /// todo_or_die::pr_closed!("tokio-rs/axum#266");
 /// // todo_or_die::pr_closed!("tokio-rs", "axum", 266);
+/// // todo_or_die::pr_closed!("https://github.com/tokio-rs/axum/pull/266");
 /// ```
 ///
 /// # Authentication
@@ -159,98 +490,2201 @@ pub fn pr_closed(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     perform_check(input, github::pr_closed)
 }
 
-/// Trigger a compile error if today is after the given date
+/// Trigger a compile error if a GitHub release has an asset whose name matches a glob pattern.
 ///
-/// Requires the `time` feature to be enabled.
+/// In the pattern `*` matches any sequence of characters and `?` matches any single character.
+///
+/// Note that this will make network requests during compile which may make your builds flaky at
+/// times.
+///
+/// Requires the `github` feature to be enabled.
 ///
 /// # Example
 ///
 /// ```compile_fail
-/// todo_or_die::after_date!(1994, 10, 22);
+/// todo_or_die::release_asset!("BurntSushi/ripgrep", "14.1.0", "*-musl.tar.gz");
 /// ```
-#[cfg(feature = "time")]
+///
+/// # Authentication
+///
+/// `release_asset` uses the same authentication as [`issue_closed!`].
+#[cfg(feature = "github")]
 #[proc_macro]
-pub fn after_date(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    perform_check(input, time::after_date)
+pub fn release_asset(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, github::release_asset)
 }
 
-/// Trigger a compile error if the latest version of a crate hosted on crates.io matches some
-/// expression.
+/// Trigger a compile error if a GitHub security advisory has been published for a repository.
+///
+/// Only advisories published on or after the `since` date (formatted as `YYYY-MM-DD`) are
+/// considered, if given. Advisories can also be filtered by their minimum `severity`, which is one
+/// of `low`, `medium`, `high`, or `critical`.
 ///
 /// Note that this will make network requests during compile which may make your builds flaky at
 /// times.
 ///
-/// Requires the `crate` feature to be enabled.
+/// Requires the `github` feature to be enabled.
 ///
 /// # Example
 ///
 /// ```compile_fail
-/// todo_or_die::crates_io!("tokio", ">=1.0");
+/// todo_or_die::ghsa_published!("tokio-rs/tokio", since = "2021-01-01", severity = "medium");
 /// ```
 ///
-/// Any version requirement supported by [`semver::VersionReq::parse`] is supported.
+/// # Authentication
 ///
-/// [`semver::VersionReq::parse`]: https://docs.rs/semver/latest/semver/struct.VersionReq.html#method.parse
-#[cfg(feature = "crate")]
+/// `ghsa_published` uses the same authentication as [`issue_closed!`].
+#[cfg(feature = "github")]
 #[proc_macro]
-pub fn crates_io(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    perform_check(input, krate::crates_io)
+pub fn ghsa_published(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, github::ghsa_published)
 }
 
-/// Trigger a compile error if the currently used version of rust used matches some expression.
+/// Trigger a compile error if a Dependabot pull request bumping a dependency to a version matching
+/// some expression has been merged.
 ///
-/// Note that release channels (like `nightly` or `beta`) are ignored.
+/// Note that this will make network requests during compile which may make your builds flaky at
+/// times.
 ///
-/// Requires the `rust` feature to be enabled.
+/// Requires the `github` feature to be enabled.
 ///
 /// # Example
 ///
 /// ```compile_fail
-/// todo_or_die::rust_version!(">1.50");
+/// todo_or_die::dependabot_merged!("tokio-rs/axum", "tower-http", ">=0.1");
 /// ```
 ///
 /// Any version requirement supported by [`semver::VersionReq::parse`] is supported.
 ///
+/// # Authentication
+///
+/// `dependabot_merged` uses the same authentication as [`issue_closed!`].
+///
 /// [`semver::VersionReq::parse`]: https://docs.rs/semver/latest/semver/struct.VersionReq.html#method.parse
-#[cfg(feature = "rust")]
+#[cfg(feature = "github")]
 #[proc_macro]
-pub fn rust_version(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    perform_check(input, rust::rust_version)
+pub fn dependabot_merged(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, github::dependabot_merged)
 }
 
-#[allow(dead_code)]
-fn perform_check<F, T>(input: proc_macro::TokenStream, f: F) -> proc_macro::TokenStream
-where
-    F: FnOnce(T) -> anyhow::Result<Option<String>>,
-    T: syn::parse::Parse,
-{
-    if std::env::var("TODO_OR_DIE_SKIP").is_ok() {
-        return Default::default();
-    }
+/// Trigger a compile error if a fork has fallen too far behind its upstream repository.
+///
+/// The default branches of both repositories are compared and the number of commits the fork is
+/// behind upstream is checked against the comparison. Supported operators are `>`, `>=`, `<`, `<=`,
+/// and `=`.
+///
+/// Note that this will make network requests during compile which may make your builds flaky at
+/// times.
+///
+/// Requires the `github` feature to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::fork_behind!("davidpdrsn/tokio", upstream = "tokio-rs/tokio", "> 50 commits");
+/// ```
+///
+/// # Authentication
+///
+/// `fork_behind` uses the same authentication as [`issue_closed!`].
+#[cfg(feature = "github")]
+#[proc_macro]
+pub fn fork_behind(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, github::fork_behind)
+}
 
-    let input = match syn::parse::<T>(input) {
-        Ok(value) => value,
-        Err(err) => {
-            let err = err.to_string();
-            return quote::quote! {
-                ::std::compile_error!(#err);
-            }
-            .into();
-        }
-    };
+/// Trigger a compile error if the latest version of a GitHub Action matches some expression.
+///
+/// The version is taken from the action repository's tags, ignoring pre-releases. Tags like `v4`
+/// are treated as `4.0.0`. Actions in a subdirectory of a repository, such as
+/// `github/codeql-action/init`, are supported.
+///
+/// Useful for crates that generate workflow files and hardcode the versions of the actions they
+/// use.
+///
+/// Note that this will make network requests during compile which may make your builds flaky at
+/// times.
+///
+/// Requires the `github` feature to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::github_action!("actions/checkout", ">=4");
+/// ```
+///
+/// Any version requirement supported by [`semver::VersionReq::parse`] is supported.
+///
+/// # Authentication
+///
+/// `github_action` uses the same authentication as [`issue_closed!`].
+///
+/// [`semver::VersionReq::parse`]: https://docs.rs/semver/latest/semver/struct.VersionReq.html#method.parse
+#[cfg(feature = "github")]
+#[proc_macro]
+pub fn github_action(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, github::github_action)
+}
 
-    match f(input) {
-        Ok(None) => {}
-        Ok(Some(msg)) => {
-            return quote::quote! {
-                ::std::compile_error!(#msg);
-            }
-            .into();
-        }
-        Err(err) => {
-            eprintln!("something went wrong\n\n{:?}", err);
-        }
-    }
+/// Trigger a compile error if a Rust RFC has been merged.
+///
+/// The RFC is given by its number, which is the number of its pull request in [rust-lang/rfcs].
+/// The message links to the RFC.
+///
+/// Note that this will make network requests during compile which may make your builds flaky at
+/// times.
+///
+/// Requires the `github` feature to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::rfc_merged!(1598); // generic associated types
+/// ```
+///
+/// # Authentication
+///
+/// `rfc_merged` uses the same authentication as [`issue_closed!`].
+///
+/// [rust-lang/rfcs]: https://github.com/rust-lang/rfcs
+#[cfg(feature = "github")]
+#[proc_macro]
+pub fn rfc_merged(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, github::rfc_merged)
+}
 
-    Default::default()
+/// Trigger a compile error if today is after the given date
+///
+/// Requires the `time` feature to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::after_date!(1994, 10, 22);
+/// ```
+///
+/// The date can also be given as an ISO 8601 string:
+///
+/// ```compile_fail
+/// todo_or_die::after_date!("1994-10-22");
+/// ```
+///
+/// # Timezones
+///
+/// By default "today" is determined in UTC, so the result doesn't depend on the timezone of the
+/// machine doing the build. A different timezone from the [tz database] can be given as the last
+/// argument:
+///
+/// ```compile_fail
+/// todo_or_die::after_date!(1994, 10, 22, "Pacific/Auckland");
+/// ```
+///
+/// The default can be changed with the `TODO_OR_DIE_TZ` environment variable, for example
+/// `TODO_OR_DIE_TZ=Europe/Stockholm`. This also applies to [`before_date!`],
+/// [`between_dates!`], [`after_duration!`], [`after_business_days!`], [`recurring!`], and
+/// [`after_datetime!`].
+///
+/// # Holidays
+///
+/// A deadline that falls on a weekend or holiday can be moved to the next business day with
+/// `holidays = ...`, so it doesn't fail a build no one is around to fix:
+///
+/// ```compile_fail
+/// todo_or_die::after_date!(1994, 12, 25, holidays = ["1994-12-25", "1994-12-26"]);
+/// ```
+///
+/// The holidays can be:
+///
+/// - A list of dates, as strings or `(year, month, day)` tuples.
+/// - The path to an iCalendar (`.ics`) file, relative to the root of your crate. Each event's
+///   start date is a holiday.
+/// - The URL of an iCalendar file. Requires the `holidays` feature.
+/// - A country code such as `"US"`, for that country's public holidays according to
+///   [Nager.Date]. Requires the `holidays` feature.
+///
+/// A missing iCalendar file is a compile error. If holidays can't be fetched over the network
+/// only weekends are skipped, and a warning is emitted.
+///
+/// This also applies to [`before_date!`], [`after_duration!`], and [`after_business_days!`].
+///
+/// # Warning before failing
+///
+/// To give yourself some runway, pass a `warn` date and a `die` date. From the `warn` date
+/// compiling emits a warning, and from the `die` date it fails:
+///
+/// ```
+/// todo_or_die::after_date!(warn: (1994, 10, 22), die: (3000, 1, 1));
+/// ```
+///
+/// The warning is a `deprecated` warning, so it's denied by `#![deny(warnings)]` and
+/// `-D warnings` like any other.
+///
+/// [tz database]: https://en.wikipedia.org/wiki/List_of_tz_database_time_zones
+/// [Nager.Date]: https://date.nager.at
+#[cfg(feature = "time")]
+#[proc_macro]
+pub fn after_date(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_local_check(input, time::after_date)
+}
+
+/// Trigger a compile error if today is before the given date
+///
+/// This is the inverse of [`after_date!`] and is useful for code that must not ship before an
+/// embargo or launch date.
+///
+/// Requires the `time` feature to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::before_date!(3000, 1, 1);
+/// ```
+#[cfg(feature = "time")]
+#[proc_macro]
+pub fn before_date(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_local_check(input, time::before_date)
+}
+
+/// Trigger a compile error if today is between two dates, inclusive.
+///
+/// Useful for seasonal code, such as freeze periods, that should be revisited while they're in
+/// effect.
+///
+/// Requires the `time` feature to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::between_dates!((2000, 1, 1), (3000, 1, 1));
+/// ```
+#[cfg(feature = "time")]
+#[proc_macro]
+pub fn between_dates(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_local_check(input, time::between_dates)
+}
+
+/// Trigger a compile error if some amount of time has passed since the given date.
+///
+/// The amount can be given in `days`, `weeks`, or `months`. The date can be a `(year, month, day)`
+/// tuple or a string like `"2025-03-01"`.
+///
+/// Useful for TODOs like "six months after we shipped X" without doing calendar math.
+///
+/// Requires the `time` feature to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::after_duration!(from = (2021, 3, 1), months = 6);
+/// ```
+#[cfg(feature = "time")]
+#[proc_macro]
+pub fn after_duration(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_local_check(input, time::after_duration)
+}
+
+/// Trigger a compile error if some number of business days have passed since the given date.
+///
+/// Weekends are skipped, as are `holidays`. See [`after_date!`] for the supported holidays.
+///
+/// Useful for deadlines like "two working weeks after the release".
+///
+/// Requires the `time` feature to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::after_business_days!(
+///     from = (2021, 12, 20),
+///     days = 10,
+///     holidays = ["2021-12-24", "2021-12-31"],
+/// );
+/// ```
+#[cfg(feature = "time")]
+#[proc_macro]
+pub fn after_business_days(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_local_check(input, time::after_business_days)
+}
+
+/// Trigger a compile error once a quarter has started.
+///
+/// By default quarters follow the calendar year. For fiscal years that start in another month
+/// pass `fiscal_start` with the name of the month. Fiscal years are named after the calendar year
+/// they start in, so with `fiscal_start = "February"` Q1 of 2026 starts on February 1st 2026.
+///
+/// Supports the same timezone and `holidays` arguments as [`after_date!`].
+///
+/// Requires the `time` feature to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::after_quarter!(2021, Q2, fiscal_start = "February");
+/// ```
+#[cfg(feature = "time")]
+#[proc_macro]
+pub fn after_quarter(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_local_check(input, time::after_quarter)
+}
+
+/// Trigger a compile error once an ISO week has started.
+///
+/// ISO weeks start on Monday, and week 1 is the week containing the first Thursday of the year.
+///
+/// Supports the same timezone and `holidays` arguments as [`after_date!`].
+///
+/// Requires the `time` feature to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::after_week!(2021, 14);
+/// ```
+#[cfg(feature = "time")]
+#[proc_macro]
+pub fn after_week(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_local_check(input, time::after_week)
+}
+
+/// Trigger a compile error once a sprint has started.
+///
+/// The sprint cadence is read from the `Cargo.toml` of your crate:
+///
+/// ```toml
+/// [package.metadata.todo-or-die.sprints]
+/// # the first day of the first sprint
+/// start = "2025-01-06"
+/// length-days = 14
+/// # the number of the first sprint, defaults to 1
+/// first = 1
+/// ```
+///
+/// Supports the same timezone and `holidays` arguments as [`after_date!`].
+///
+/// Requires the `time` feature to be enabled.
+///
+/// # Example
+///
+/// ```ignore
+/// todo_or_die::after_sprint!(42);
+/// ```
+#[cfg(feature = "time")]
+#[proc_macro]
+pub fn after_sprint(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_local_check(input, time::after_sprint)
+}
+
+/// Trigger a compile error some number of days after the macro was first compiled.
+///
+/// The first time a `dies_in!` with a given `id` is compiled today's date is recorded in a
+/// `.todo-or-die` file in the root of your crate. Commit that file so everyone, including CI,
+/// shares the same deadline. The `id` must be unique within the crate.
+///
+/// The file is only written when compiling your own crates, not when they're compiled as a
+/// dependency or as doctests. A different file can be used by setting `TODO_OR_DIE_FILE` to a
+/// path, relative to the root of your crate.
+///
+/// Useful for temporary hacks where you don't want to pick an exact date.
+///
+/// Requires the `time` feature to be enabled.
+///
+/// # Example
+///
+/// ```ignore
+/// todo_or_die::dies_in!(days = 90, id = "remove-legacy-auth");
+/// ```
+///
+/// The `id` is the same as the `id` all macros accept.
+#[cfg(feature = "time")]
+#[proc_macro]
+pub fn dies_in(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check_with(input, |input: time::DiesIn, modifiers| {
+        time::dies_in(input.or_id(modifiers.id.as_deref()))
+    })
+}
+
+/// Trigger a compile error during a recurring window of days.
+///
+/// The window starts on each day matching a [cron expression] and lasts for `for_days` days. Only
+/// the day of month, month, and day of week fields are used, the minute and hour fields must be
+/// valid but are otherwise ignored.
+///
+/// Useful for periodic chores such as reviewing your license inventory every January.
+///
+/// Requires the `time` feature to be enabled.
+///
+/// # Example
+///
+/// ```ignore
+/// // the first two weeks of January and July
+/// todo_or_die::recurring!("0 0 1 1,7 *", for_days = 14);
+/// ```
+///
+/// [cron expression]: https://en.wikipedia.org/wiki/Cron
+#[cfg(feature = "time")]
+#[proc_macro]
+pub fn recurring(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_local_check(input, time::recurring)
+}
+
+/// Trigger a compile error if the current time is after the given date and time.
+///
+/// The time can be given as year, month, day, hour, minute, and second, optionally followed by
+/// the name of a timezone from the [tz database]. Without a timezone UTC is used, unless
+/// overridden with `TODO_OR_DIE_TZ`. See [`after_date!`] for details.
+///
+/// Requires the `time` feature to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::after_datetime!(2021, 6, 1, 17, 0, 0, "Europe/Stockholm");
+/// ```
+///
+/// An [RFC 3339] string is also supported:
+///
+/// ```compile_fail
+/// todo_or_die::after_datetime!("2021-06-01T17:00:00+02:00");
+/// ```
+///
+/// [tz database]: https://en.wikipedia.org/wiki/List_of_tz_database_time_zones
+/// [RFC 3339]: https://www.rfc-editor.org/rfc/rfc3339
+#[cfg(feature = "time")]
+#[proc_macro]
+pub fn after_datetime(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_local_check(input, time::after_datetime)
+}
+
+/// Trigger a compile error if the latest version of a crate hosted on crates.io matches some
+/// expression.
+///
+/// Note that this will make network requests during compile which may make your builds flaky at
+/// times.
+///
+/// Requires the `crate` feature to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::crates_io!("tokio", ">=1.0");
+/// ```
+///
+/// Any version requirement supported by [`semver::VersionReq::parse`] is supported.
+///
+/// # Pre-releases and yanked versions
+///
+/// The latest version is the greatest version that isn't a pre-release and hasn't been yanked.
+/// Pass `include_prereleases` and/or `include_yanked` to consider those as well:
+///
+/// ```compile_fail
+/// todo_or_die::crates_io!("tokio", ">=1.0", include_prereleases, include_yanked);
+/// ```
+///
+/// [`semver::VersionReq::parse`]: https://docs.rs/semver/latest/semver/struct.VersionReq.html#method.parse
+#[cfg(feature = "crate")]
+#[proc_macro]
+pub fn crates_io(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, krate::crates_io)
+}
+
+/// Trigger a compile error if the latest version of a crate in a Cargo registry matches some
+/// expression.
+///
+/// Unlike [`crates_io!`] this supports alternative and private registries. The registry is given
+/// as the URL of its [sparse index] and defaults to crates.io. Like [`crates_io!`] yanked versions
+/// and pre-releases are ignored unless `include_yanked` or `include_prereleases` is given.
+///
+/// Note that this will make network requests during compile which may make your builds flaky at
+/// times.
+///
+/// Requires the `crate` feature to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::registry!("tokio", ">=1.0", registry = "https://index.crates.io");
+/// ```
+///
+/// Any version requirement supported by [`semver::VersionReq::parse`] is supported.
+///
+/// # Authentication
+///
+/// For registries that require authentication set the `TODO_OR_DIE_CARGO_REGISTRY_TOKEN`
+/// environment variable. It's sent as the `Authorization` header, the same way Cargo does.
+///
+/// [sparse index]: https://doc.rust-lang.org/cargo/reference/registry-index.html#sparse-protocol
+/// [`semver::VersionReq::parse`]: https://docs.rs/semver/latest/semver/struct.VersionReq.html#method.parse
+#[cfg(feature = "crate")]
+#[proc_macro]
+pub fn registry(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, krate::registry)
+}
+
+/// Trigger a compile error if a version of a crate matching some expression has been yanked.
+///
+/// Useful for surfacing pinned dependencies that have been yanked, for example due to security
+/// issues. Like [`registry!`] an optional `registry = "..."` argument can be given to check
+/// alternative registries.
+///
+/// Note that this will make network requests during compile which may make your builds flaky at
+/// times.
+///
+/// Requires the `crate` feature to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::crate_yanked!("tokio", "=1.0.0");
+/// ```
+///
+/// Any version requirement supported by [`semver::VersionReq::parse`] is supported.
+///
+/// [`semver::VersionReq::parse`]: https://docs.rs/semver/latest/semver/struct.VersionReq.html#method.parse
+#[cfg(feature = "crate")]
+#[proc_macro]
+pub fn crate_yanked(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, krate::crate_yanked)
+}
+
+/// Trigger a compile error if a specific version of a crate has been published on crates.io.
+///
+/// Yanked versions don't count as published.
+///
+/// Note that this will make network requests during compile which may make your builds flaky at
+/// times.
+///
+/// Requires the `crate` feature to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::crate_published!("tokio", "1.0.0");
+/// ```
+#[cfg(feature = "crate")]
+#[proc_macro]
+pub fn crate_published(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, krate::crate_published)
+}
+
+/// Trigger a compile error if the latest version of a crate on crates.io declares a
+/// `rust-version` matching some expression.
+///
+/// Useful for knowing when a dependency will force, or allow, bumping your own minimum supported
+/// Rust version. Nothing happens if the latest version doesn't declare a `rust-version`. A
+/// `rust-version` such as `1.80` is treated as `1.80.0`.
+///
+/// Note that this will make network requests during compile which may make your builds flaky at
+/// times.
+///
+/// Requires the `crate` feature to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::crate_msrv!("tokio", ">=1.56");
+/// ```
+///
+/// Any version requirement supported by [`semver::VersionReq::parse`] is supported.
+///
+/// [`semver::VersionReq::parse`]: https://docs.rs/semver/latest/semver/struct.VersionReq.html#method.parse
+#[cfg(feature = "crate")]
+#[proc_macro]
+pub fn crate_msrv(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, krate::crate_msrv)
+}
+
+/// Trigger a compile error if the number of downloads of a crate on crates.io matches some
+/// comparison.
+///
+/// Supported operators are `>`, `>=`, `<`, `<=`, and `=`.
+///
+/// Note that this will make network requests during compile which may make your builds flaky at
+/// times.
+///
+/// Requires the `crate` feature to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::crate_downloads!("tokio", ">= 100000");
+/// ```
+#[cfg(feature = "crate")]
+#[proc_macro]
+pub fn crate_downloads(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, krate::crate_downloads)
+}
+
+/// Trigger a compile error if a version of a crate on crates.io was published more than some
+/// number of months ago.
+///
+/// The greatest version matching the requirement that hasn't been yanked is checked, so pinning an
+/// exact version with `=` is typical.
+///
+/// Useful for periodically re-evaluating pinned dependencies.
+///
+/// Note that this will make network requests during compile which may make your builds flaky at
+/// times.
+///
+/// Requires the `crate` feature to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::crate_release_age!("tokio", "=1.0.0", months = 6);
+/// ```
+///
+/// Any version requirement supported by [`semver::VersionReq::parse`] is supported.
+///
+/// [`semver::VersionReq::parse`]: https://docs.rs/semver/latest/semver/struct.VersionReq.html#method.parse
+#[cfg(feature = "crate")]
+#[proc_macro]
+pub fn crate_release_age(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, krate::crate_release_age)
+}
+
+/// Trigger a compile error if the repository of a crate on crates.io has been archived.
+///
+/// The repository is taken from the crate's metadata on crates.io. Repositories on GitHub and
+/// GitLab are supported.
+///
+/// Note that this will make network requests during compile which may make your builds flaky at
+/// times.
+///
+/// Requires the `crate` and `github` features to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::crate_repo_archived!("failure");
+/// ```
+///
+/// # Authentication
+///
+/// Requests to GitHub use the same authentication as [`issue_closed!`].
+#[cfg(all(feature = "crate", feature = "github"))]
+#[proc_macro]
+pub fn crate_repo_archived(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, krate::crate_repo_archived)
+}
+
+/// Trigger a compile error if any version of a crate has been published on crates.io.
+///
+/// Useful when waiting for someone to publish a crate they've promised, or to release a name
+/// that's been squatted.
+///
+/// Note that this will make network requests during compile which may make your builds flaky at
+/// times.
+///
+/// Requires the `crate` feature to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::crate_exists!("tokio");
+/// ```
+#[cfg(feature = "crate")]
+#[proc_macro]
+pub fn crate_exists(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, krate::crate_exists)
+}
+
+/// Trigger a compile error if the currently used version of rust used matches some expression.
+///
+/// Note that release channels (like `nightly` or `beta`) are ignored unless `include_prereleases`
+/// is given.
+///
+/// Requires the `rust` feature to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::rust_version!(">1.50");
+/// ```
+///
+/// Any version requirement supported by [`semver::VersionReq::parse`] is supported.
+///
+/// # Pre-releases
+///
+/// With `include_prereleases` non-stable toolchains are treated as pre-releases whose identifiers
+/// are the channel and the commit date, such as `1.85.0-nightly.20241201`. This makes it possible
+/// to check for specific nightly toolchains:
+///
+/// ```ignore
+/// todo_or_die::rust_version!(">=1.85.0-nightly.20241201", include_prereleases);
+/// ```
+///
+/// Note that, following semver, a requirement like `>=1.85.0-nightly` only matches pre-releases of
+/// `1.85.0` itself, along with any stable version from `1.85.0` onwards.
+///
+/// [`semver::VersionReq::parse`]: https://docs.rs/semver/latest/semver/struct.VersionReq.html#method.parse
+#[cfg(feature = "rust")]
+#[proc_macro]
+pub fn rust_version(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, rust::rust_version)
+}
+
+/// Trigger a compile error if the active rust toolchain is more than some number of months old.
+///
+/// The age is based on the commit date reported by `rustc -vV`.
+///
+/// Useful for catching toolchains pinned in `rust-toolchain.toml` that have been forgotten about.
+///
+/// Requires the `rust` feature to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::toolchain_age!(months = 0);
+/// ```
+#[cfg(feature = "rust")]
+#[proc_macro]
+pub fn toolchain_age(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, rust::toolchain_age)
+}
+
+/// Trigger a compile error if the version of clippy used matches some expression.
+///
+/// This only does something when running `cargo clippy`. Otherwise it always succeeds.
+///
+/// Useful for removing `#[allow(...)]` attributes that work around false positives once a fixed
+/// version of clippy has been released.
+///
+/// Requires the `rust` feature to be enabled.
+///
+/// # Example
+///
+/// ```
+/// // only fails when running `cargo clippy`
+/// todo_or_die::clippy_version!(">=0.1.85");
+/// ```
+///
+/// Any version requirement supported by [`semver::VersionReq::parse`] is supported.
+///
+/// [`semver::VersionReq::parse`]: https://docs.rs/semver/latest/semver/struct.VersionReq.html#method.parse
+#[cfg(feature = "rust")]
+#[proc_macro]
+pub fn clippy_version(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, rust::clippy_version)
+}
+
+/// Trigger a compile error if the version of LLVM bundled with rustc matches some expression.
+///
+/// The version is taken from `rustc -vV`.
+///
+/// Useful for removing codegen workarounds once the bundled LLVM has been fixed.
+///
+/// Requires the `rust` feature to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::llvm_version!(">=10");
+/// ```
+///
+/// Any version requirement supported by [`semver::VersionReq::parse`] is supported.
+///
+/// [`semver::VersionReq::parse`]: https://docs.rs/semver/latest/semver/struct.VersionReq.html#method.parse
+#[cfg(feature = "rust")]
+#[proc_macro]
+pub fn llvm_version(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, rust::llvm_version)
+}
+
+/// Trigger a compile error if building with a toolchain from a particular release channel.
+///
+/// The channel is one of `stable`, `beta`, `nightly`, or `dev`.
+///
+/// Useful for making sure temporary workarounds for one channel don't end up being used on
+/// another.
+///
+/// Requires the `rust` feature to be enabled.
+///
+/// # Example
+///
+/// ```ignore
+/// // make sure this nightly-only hack is never built on stable
+/// todo_or_die::rust_channel!("stable");
+/// ```
+#[cfg(feature = "rust")]
+#[proc_macro]
+pub fn rust_channel(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, rust::rust_channel)
+}
+
+/// Trigger a compile error if a version of rust has been released.
+///
+/// Unlike [`rust_version!`] this doesn't depend on the toolchain you're compiling with, but on the
+/// latest stable release of rust.
+///
+/// Note that this will make network requests during compile which may make your builds flaky at
+/// times.
+///
+/// Requires the `rust-upstream` feature to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::after_rust_release!("1.80");
+/// ```
+#[cfg(feature = "rust-upstream")]
+#[proc_macro]
+pub fn after_rust_release(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, rust_upstream::after_rust_release)
+}
+
+/// Trigger a compile error once some number of stable releases of rust have shipped after a given
+/// version.
+///
+/// Useful for support window policies such as "we support the last 6 stable releases".
+///
+/// Note that this will make network requests during compile which may make your builds flaky at
+/// times.
+///
+/// Requires the `rust-upstream` feature to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::rust_releases_since!("1.74", 6);
+/// ```
+#[cfg(feature = "rust-upstream")]
+#[proc_macro]
+pub fn rust_releases_since(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, rust_upstream::rust_releases_since)
+}
+
+/// Trigger a compile error if the latest stable version of rust is more than some number of
+/// releases ahead of your minimum supported rust version.
+///
+/// The minimum supported rust version is read from `rust-version` in `Cargo.toml`.
+///
+/// Useful for enforcing a policy such as "our MSRV is at most 4 releases behind stable".
+///
+/// Note that this will make network requests during compile which may make your builds flaky at
+/// times.
+///
+/// Requires the `rust-upstream` feature to be enabled.
+///
+/// # Example
+///
+/// ```ignore
+/// // with `rust-version = "1.70"` in Cargo.toml
+/// todo_or_die::msrv_drift!(releases = 4);
+/// ```
+#[cfg(feature = "rust-upstream")]
+#[proc_macro]
+pub fn msrv_drift(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, rust_upstream::msrv_drift)
+}
+
+/// Trigger a compile error if a nightly feature has been stabilized.
+///
+/// The status of the feature is taken from the compiler's feature gate tables on the `master`
+/// branch of [rust-lang/rust], so this fires once the stabilization has been merged, before it
+/// reaches a stable release.
+///
+/// Useful for removing `#![feature(...)]` attributes once they're no longer needed.
+///
+/// Note that this will make network requests during compile which may make your builds flaky at
+/// times.
+///
+/// Requires the `rust-upstream` feature to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::feature_stabilized!("generic_associated_types");
+/// ```
+///
+/// [rust-lang/rust]: https://github.com/rust-lang/rust
+#[cfg(feature = "rust-upstream")]
+#[proc_macro]
+pub fn feature_stabilized(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, rust_upstream::feature_stabilized)
+}
+
+/// Trigger a compile error if a nightly feature has been removed or renamed.
+///
+/// Like [`feature_stabilized!`] the status of the feature is taken from the compiler's feature
+/// gate tables on the `master` branch of [rust-lang/rust], so this fires before the change reaches
+/// a nightly toolchain you're likely to be using. Features that have been stabilized aren't
+/// considered removed, and neither are library features such as `iter_array_chunks`, since only
+/// the compiler's list of removed features is checked.
+///
+/// Note that this will make network requests during compile which may make your builds flaky at
+/// times.
+///
+/// Requires the `rust-upstream` feature to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::feature_removed!("box_syntax");
+/// ```
+///
+/// [rust-lang/rust]: https://github.com/rust-lang/rust
+#[cfg(feature = "rust-upstream")]
+#[proc_macro]
+pub fn feature_removed(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, rust_upstream::feature_removed)
+}
+
+/// Trigger a compile error if a cargo unstable flag (`-Z ...`) has been stabilized.
+///
+/// The status of the flag is taken from cargo's source on the `master` branch of [rust-lang/cargo].
+///
+/// Useful for updating CI scripts that use `-Z` flags once they're no longer needed.
+///
+/// Note that this will make network requests during compile which may make your builds flaky at
+/// times.
+///
+/// Requires the `rust-upstream` feature to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::cargo_unstable_stabilized!("sparse-registry");
+/// ```
+///
+/// [rust-lang/cargo]: https://github.com/rust-lang/cargo
+#[cfg(feature = "rust-upstream")]
+#[proc_macro]
+pub fn cargo_unstable_stabilized(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, rust_upstream::cargo_unstable_stabilized)
+}
+
+/// Trigger a compile error if a target has reached a given [support tier] (or a better one).
+///
+/// The tier is taken from the platform support docs on the `master` branch of rust-lang/rust.
+/// Targets that aren't listed never trigger an error.
+///
+/// Useful for knowing when to promote a target in your CI matrix.
+///
+/// Note that this will make network requests during compile which may make your builds flaky at
+/// times.
+///
+/// Requires the `rust-upstream` feature to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// // `tier1`, `tier2`, and `tier3` are supported
+/// todo_or_die::target_tier!("x86_64-unknown-linux-gnu", "tier2");
+/// ```
+///
+/// [support tier]: https://doc.rust-lang.org/rustc/platform-support.html
+#[cfg(feature = "rust-upstream")]
+#[proc_macro]
+pub fn target_tier(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, rust_upstream::target_tier)
+}
+
+/// Trigger a compile error if a Rust edition is available on stable.
+///
+/// This is determined from the compiler source of the latest stable release, rather than the
+/// version of rust you're compiling with.
+///
+/// Useful for reminding you to schedule migrating to a new edition.
+///
+/// Note that this will make network requests during compile which may make your builds flaky at
+/// times.
+///
+/// Requires the `rust-upstream` feature to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::edition_available!("2021");
+/// ```
+#[cfg(feature = "rust-upstream")]
+#[proc_macro]
+pub fn edition_available(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, rust_upstream::edition_available)
+}
+
+/// Trigger a compile error if the latest version of a package on npm matches some expression.
+///
+/// Note that this will make network requests during compile which may make your builds flaky at
+/// times.
+///
+/// Requires the `npm` feature to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::npm!("react", ">=18");
+/// ```
+///
+/// Any version requirement supported by [`semver::VersionReq::parse`] is supported.
+///
+/// [`semver::VersionReq::parse`]: https://docs.rs/semver/latest/semver/struct.VersionReq.html#method.parse
+#[cfg(feature = "npm")]
+#[proc_macro]
+pub fn npm(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, npm::npm)
+}
+
+/// Trigger a compile error if the latest version of a package on PyPI matches some expression.
+///
+/// Versions are converted to semver on a best-effort basis. For example `2.0` becomes `2.0.0` and
+/// `2.0rc1` becomes `2.0.0-rc1`.
+///
+/// Note that this will make network requests during compile which may make your builds flaky at
+/// times.
+///
+/// Requires the `pypi` feature to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::pypi!("numpy", ">=1.0");
+/// ```
+///
+/// Any version requirement supported by [`semver::VersionReq::parse`] is supported.
+///
+/// [`semver::VersionReq::parse`]: https://docs.rs/semver/latest/semver/struct.VersionReq.html#method.parse
+#[cfg(feature = "pypi")]
+#[proc_macro]
+pub fn pypi(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, pypi::pypi)
+}
+
+/// Trigger a compile error if the latest version of a gem on RubyGems matches some expression.
+///
+/// Note that this will make network requests during compile which may make your builds flaky at
+/// times.
+///
+/// Requires the `rubygems` feature to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::rubygems!("rails", ">=7.0");
+/// ```
+///
+/// Any version requirement supported by [`semver::VersionReq::parse`] is supported.
+///
+/// [`semver::VersionReq::parse`]: https://docs.rs/semver/latest/semver/struct.VersionReq.html#method.parse
+#[cfg(feature = "rubygems")]
+#[proc_macro]
+pub fn rubygems(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, rubygems::rubygems)
+}
+
+/// Trigger a compile error if a tag exists for an image on Docker Hub.
+///
+/// Images without a namespace, such as `debian`, are assumed to be official images in the
+/// `library` namespace.
+///
+/// If `digest` is given the compile error is instead triggered if the tag exists and its digest is
+/// different, which is useful for noticing when a tag has been updated.
+///
+/// Note that this will make network requests during compile which may make your builds flaky at
+/// times.
+///
+/// Requires the `docker` feature to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::docker_tag!("library/debian", "bookworm");
+/// // todo_or_die::docker_tag!("library/debian", "bookworm", digest = "sha256:...");
+/// ```
+#[cfg(feature = "docker")]
+#[proc_macro]
+pub fn docker_tag(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, docker::docker_tag)
+}
+
+/// Trigger a compile error if the latest version of a artifact on Maven Central matches some expression.
+///
+/// The artifact is given as `group:artifact`.
+///
+/// Versions are converted to semver on a best-effort basis. Qualifiers that don't indicate
+/// pre-releases, such as `-jre` in `33.0.0-jre`, are ignored.
+///
+/// Note that this will make network requests during compile which may make your builds flaky at
+/// times.
+///
+/// Requires the `maven` feature to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::maven!("com.google.guava:guava", ">=30");
+/// ```
+///
+/// Any version requirement supported by [`semver::VersionReq::parse`] is supported.
+///
+/// [`semver::VersionReq::parse`]: https://docs.rs/semver/latest/semver/struct.VersionReq.html#method.parse
+#[cfg(feature = "maven")]
+#[proc_macro]
+pub fn maven(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, maven::maven)
+}
+
+/// Trigger a compile error if the latest version of a package on NuGet matches some expression.
+///
+/// Note that this will make network requests during compile which may make your builds flaky at
+/// times.
+///
+/// Requires the `nuget` feature to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::nuget!("Newtonsoft.Json", ">=13");
+/// ```
+///
+/// Any version requirement supported by [`semver::VersionReq::parse`] is supported.
+///
+/// [`semver::VersionReq::parse`]: https://docs.rs/semver/latest/semver/struct.VersionReq.html#method.parse
+#[cfg(feature = "nuget")]
+#[proc_macro]
+pub fn nuget(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, nuget::nuget)
+}
+
+/// Trigger a compile error if the latest version of a package on Hex matches some expression.
+///
+/// Note that this will make network requests during compile which may make your builds flaky at
+/// times.
+///
+/// Requires the `hex-pm` feature to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::hex_pm!("phoenix", ">=1.0");
+/// ```
+///
+/// Any version requirement supported by [`semver::VersionReq::parse`] is supported.
+///
+/// [`semver::VersionReq::parse`]: https://docs.rs/semver/latest/semver/struct.VersionReq.html#method.parse
+#[cfg(feature = "hex-pm")]
+#[proc_macro]
+pub fn hex_pm(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, hex_pm::hex_pm)
+}
+
+/// Trigger a compile error if the latest version of a package on Packagist matches some expression.
+///
+/// Note that this will make network requests during compile which may make your builds flaky at
+/// times.
+///
+/// Requires the `packagist` feature to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::packagist!("laravel/framework", ">=10");
+/// ```
+///
+/// Any version requirement supported by [`semver::VersionReq::parse`] is supported.
+///
+/// [`semver::VersionReq::parse`]: https://docs.rs/semver/latest/semver/struct.VersionReq.html#method.parse
+#[cfg(feature = "packagist")]
+#[proc_macro]
+pub fn packagist(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, packagist::packagist)
+}
+
+/// Trigger a compile error if the latest version of a module on the Go module proxy matches some expression.
+///
+/// Note that this will make network requests during compile which may make your builds flaky at
+/// times.
+///
+/// Requires the `go` feature to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::go_module!("github.com/hashicorp/terraform", ">=1.0");
+/// ```
+///
+/// Any version requirement supported by [`semver::VersionReq::parse`] is supported.
+///
+/// [`semver::VersionReq::parse`]: https://docs.rs/semver/latest/semver/struct.VersionReq.html#method.parse
+#[cfg(feature = "go")]
+#[proc_macro]
+pub fn go_module(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, go::go_module)
+}
+
+/// Trigger a compile error if the latest version of a package on nixpkgs matches some expression.
+///
+/// The channel defaults to `nixos-unstable`. Stable channels are given as `nixos-YY.MM`, for
+/// example `nixos-24.11`. Versions are looked up through [repology](https://repology.org).
+///
+/// Note that this will make network requests during compile which may make your builds flaky at
+/// times.
+///
+/// Requires the `nixpkgs` feature to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::nixpkgs!("ripgrep", channel = "nixos-unstable", ">=13");
+/// ```
+///
+/// Any version requirement supported by [`semver::VersionReq::parse`] is supported.
+///
+/// [`semver::VersionReq::parse`]: https://docs.rs/semver/latest/semver/struct.VersionReq.html#method.parse
+#[cfg(feature = "nixpkgs")]
+#[proc_macro]
+pub fn nixpkgs(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, nixpkgs::nixpkgs)
+}
+
+/// Trigger a compile error if the latest version of a package on Arch Linux matches some expression.
+///
+/// Packages in the [AUR](https://aur.archlinux.org) can be checked by adding `aur`:
+///
+/// ```compile_fail
+/// todo_or_die::archlinux!("yay", ">=10", aur);
+/// ```
+///
+/// Note that this will make network requests during compile which may make your builds flaky at
+/// times.
+///
+/// Requires the `archlinux` feature to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::archlinux!("openssl", ">=3.0");
+/// ```
+///
+/// Any version requirement supported by [`semver::VersionReq::parse`] is supported.
+///
+/// [`semver::VersionReq::parse`]: https://docs.rs/semver/latest/semver/struct.VersionReq.html#method.parse
+#[cfg(feature = "archlinux")]
+#[proc_macro]
+pub fn archlinux(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, archlinux::archlinux)
+}
+
+/// Trigger a compile error if the latest version of a provider or module on the Terraform Registry matches some expression.
+///
+/// Providers are given as `namespace/provider` and modules as `namespace/module/provider`.
+///
+/// Note that this will make network requests during compile which may make your builds flaky at
+/// times.
+///
+/// Requires the `terraform` feature to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::terraform_registry!("hashicorp/aws", ">=5.0");
+/// ```
+///
+/// Any version requirement supported by [`semver::VersionReq::parse`] is supported.
+///
+/// [`semver::VersionReq::parse`]: https://docs.rs/semver/latest/semver/struct.VersionReq.html#method.parse
+#[cfg(feature = "terraform")]
+#[proc_macro]
+pub fn terraform_registry(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, terraform::terraform_registry)
+}
+
+/// Trigger a compile error if the latest version of a chart in a Helm chart repository matches some expression.
+///
+/// The first argument is the URL of the chart repository, which must serve an `index.yaml`.
+/// Pre-release versions are ignored.
+///
+/// Note that this will make network requests during compile which may make your builds flaky at
+/// times.
+///
+/// Requires the `helm` feature to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::helm_chart!("https://charts.bitnami.com/bitnami", "postgresql", ">=10");
+/// ```
+///
+/// Any version requirement supported by [`semver::VersionReq::parse`] is supported.
+///
+/// [`semver::VersionReq::parse`]: https://docs.rs/semver/latest/semver/struct.VersionReq.html#method.parse
+#[cfg(feature = "helm")]
+#[proc_macro]
+pub fn helm_chart(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, helm::helm_chart)
+}
+
+/// Trigger a compile error if a security advisory for a crate has been published in the [RustSec
+/// advisory database].
+///
+/// Only advisories published on or after the `since` date (formatted as `YYYY-MM-DD`) are
+/// considered, if given. If `version` is given only advisories affecting that version of the
+/// crate are considered. Informational and withdrawn advisories are ignored.
+///
+/// Note that this will make network requests during compile which may make your builds flaky at
+/// times.
+///
+/// Requires the `rustsec` feature to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::rustsec!("openssl", since = "2023-01-01", version = "0.10.45");
+/// ```
+///
+/// [RustSec advisory database]: https://rustsec.org
+#[cfg(feature = "rustsec")]
+#[proc_macro]
+pub fn rustsec(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, rustsec::rustsec)
+}
+
+/// Trigger a compile error if a crate has been marked as unmaintained in the [RustSec advisory
+/// database].
+///
+/// Note that this will make network requests during compile which may make your builds flaky at
+/// times.
+///
+/// Requires the `rustsec` feature to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::crate_unmaintained!("term");
+/// ```
+///
+/// [RustSec advisory database]: https://rustsec.org
+#[cfg(feature = "rustsec")]
+#[proc_macro]
+pub fn crate_unmaintained(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, rustsec::crate_unmaintained)
+}
+
+/// Trigger a compile error if the version of a dependency in `Cargo.lock` matches some expression.
+///
+/// `Cargo.lock` is found by looking in the directory of the crate being compiled and its parents,
+/// so this works in workspaces. If several versions of the dependency are locked all of them must
+/// match. No network requests are made.
+///
+/// Useful for removing workarounds once you've actually upgraded a dependency.
+///
+/// Requires the `lockfile` feature to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::dependency_version!("anyhow", ">=1.0");
+/// ```
+///
+/// Any version requirement supported by [`semver::VersionReq::parse`] is supported.
+///
+/// [`semver::VersionReq::parse`]: https://docs.rs/semver/latest/semver/struct.VersionReq.html#method.parse
+#[cfg(feature = "lockfile")]
+#[proc_macro]
+pub fn dependency_version(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, lockfile::dependency_version)
+}
+
+/// Trigger a compile error if a crate is no longer in `Cargo.lock`.
+///
+/// Useful for removing compatibility code once you've finished migrating off a dependency. Both
+/// direct and transitive dependencies are considered. No network requests are made.
+///
+/// Requires the `lockfile` feature to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::dependency_gone!("this-crate-is-not-a-dependency");
+/// ```
+#[cfg(feature = "lockfile")]
+#[proc_macro]
+pub fn dependency_gone(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, lockfile::dependency_gone)
+}
+
+/// Trigger a compile error if `Cargo.lock` hasn't been updated in some number of days.
+///
+/// If `Cargo.lock` is tracked by git the time of the last commit that changed it is used,
+/// otherwise its modification time is used. No network requests are made.
+///
+/// Useful for nudging services that are rarely touched to refresh their dependencies.
+///
+/// Requires the `lockfile` feature to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::lockfile_age!(days = 0);
+/// ```
+#[cfg(feature = "lockfile")]
+#[proc_macro]
+pub fn lockfile_age(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, lockfile::lockfile_age)
+}
+
+/// Trigger a compile error if any direct dependency has a newer version on crates.io.
+///
+/// The dependencies in `Cargo.toml`, including dev and build dependencies, are compared against
+/// the versions locked in `Cargo.lock`. Pass `major_only` to only consider semver incompatible
+/// releases, such as `2.0.0` for `1.x` or `0.5.0` for `0.4.x`. Path, git, and alternative registry
+/// dependencies are ignored.
+///
+/// Note that this will make network requests during compile which may make your builds flaky at
+/// times.
+///
+/// Requires the `crate` and `lockfile` features to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::deps_outdated!(major_only);
+/// ```
+#[cfg(all(feature = "crate", feature = "lockfile"))]
+#[proc_macro]
+pub fn deps_outdated(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, lockfile::deps_outdated)
+}
+
+/// Trigger a compile error if the latest version tagged in a git repository matches some
+/// expression.
+///
+/// The tags are listed with `git ls-remote` so this works with any git host and requires `git` to
+/// be installed. Tags that don't look like versions, and pre-releases, are ignored.
+///
+/// Useful for dependencies that are used via `git = "..."` and never published to crates.io.
+///
+/// Note that this will make network requests during compile which may make your builds flaky at
+/// times.
+///
+/// Requires the `git` feature to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::git_repo_version!("https://github.com/tokio-rs/axum", ">=0.5");
+/// ```
+///
+/// Any version requirement supported by [`semver::VersionReq::parse`] is supported.
+///
+/// [`semver::VersionReq::parse`]: https://docs.rs/semver/latest/semver/struct.VersionReq.html#method.parse
+#[cfg(feature = "git")]
+#[proc_macro]
+pub fn git_repo_version(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, git::git_repo_version)
+}
+
+/// Trigger a compile error if the line the macro is on was committed more than some number of
+/// days ago.
+///
+/// The age is determined with `git blame` so this requires `git` to be installed. Lines that
+/// haven't been committed yet are never considered old. It also requires Rust 1.88 or newer.
+///
+/// Useful for reminders that age with the code rather than the calendar.
+///
+/// Requires the `git` feature to be enabled.
+///
+/// # Example
+///
+/// ```
+/// todo_or_die::line_older_than!(days = 180);
+/// ```
+#[cfg(feature = "git")]
+#[proc_macro]
+pub fn line_older_than(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, git::line_older_than)
+}
+
+/// Trigger a compile error some number of days after a commit was made.
+///
+/// The commit date is looked up in the git repository your crate is in. If the commit isn't found
+/// there, for example because of a shallow clone on CI, and `repo = "org/repo"` is given it's
+/// looked up on GitHub instead, which requires the `github` feature.
+///
+/// Useful for tying deadlines to when a change actually landed.
+///
+/// Requires the `git` feature to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::after_commit!(
+///     sha = "c01efc669f09508b55eced32d3c88702578a7c3e",
+///     days = 30,
+///     repo = "rust-lang/rust",
+/// );
+/// ```
+///
+/// # Authentication
+///
+/// Looking up commits on GitHub uses the same authentication as [`issue_closed!`].
+#[cfg(feature = "git")]
+#[proc_macro]
+pub fn after_commit(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, git::after_commit)
+}
+
+/// Trigger a compile error if a server's TLS certificate expires within some number of days.
+///
+/// Connects to the host, port 443 unless another is given such as `"api.example.com:8443"`, and
+/// looks at the certificate it presents. `days_before` defaults to 30. This is useful for
+/// reminders to rotate pinned certificates.
+///
+/// The certificate isn't verified, so expired and self-signed certificates are also checked.
+///
+/// Note that this will make network requests during compile which may make your builds flaky
+/// at times.
+///
+/// Requires the `tls` feature to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::cert_expires!("expired.badssl.com", days_before = 30);
+/// ```
+#[cfg(feature = "tls")]
+#[proc_macro]
+pub fn cert_expires(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, tls::cert_expires)
+}
+
+/// Trigger a compile error if a release of a product has reached, or is within `days_before` days
+/// of, end-of-life according to [endoflife.date].
+///
+/// This covers operating systems, databases, language runtimes, and many other products. The
+/// product and release cycle are the same as in the endoflife.date URLs, for example
+/// <https://endoflife.date/ubuntu>.
+///
+/// Note that this will make network requests during compile which may make your builds flaky
+/// at times.
+///
+/// Requires the `endoflife` feature to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::eol!("ubuntu", "22.04", days_before = 365);
+/// ```
+///
+/// [endoflife.date]: https://endoflife.date
+#[cfg(feature = "endoflife")]
+#[proc_macro]
+pub fn eol(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, endoflife::eol)
+}
+
+/// Trigger a compile error if a major version of Node.js has reached, or is within `days_before`
+/// days of, end-of-life.
+///
+/// Useful for native Node.js addons, such as those built with napi-rs, to get a heads up before
+/// the Node.js version they target stops being supported. The dates come from [endoflife.date],
+/// same as [`eol!`].
+///
+/// Note that this will make network requests during compile which may make your builds flaky
+/// at times.
+///
+/// Requires the `endoflife` feature to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::nodejs_eol!("16", days_before = 60);
+/// ```
+///
+/// [endoflife.date]: https://endoflife.date
+#[cfg(feature = "endoflife")]
+#[proc_macro]
+pub fn nodejs_eol(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, endoflife::nodejs_eol)
+}
+
+/// Trigger a compile error if a minor version of Kubernetes has reached, or is within
+/// `days_before` days of, end-of-life.
+///
+/// Useful for operators and controllers that are only compatible with some Kubernetes versions.
+/// The dates come from [endoflife.date], same as [`eol!`].
+///
+/// Note that this will make network requests during compile which may make your builds flaky
+/// at times.
+///
+/// Requires the `endoflife` feature to be enabled.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::kubernetes_eol!("1.20", days_before = 30);
+/// ```
+///
+/// [endoflife.date]: https://endoflife.date
+#[cfg(feature = "endoflife")]
+#[proc_macro]
+pub fn kubernetes_eol(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, endoflife::kubernetes_eol)
+}
+
+/// Perform a check given in a struct-like form.
+///
+/// `when` is any of the other macros, without the `!`, and its arguments. `message`, `owner`,
+/// and `link` are optional and added to the compile error.
+///
+/// `check!` expands to a call to the macro in `when`, with `message = "..."`, `owner = "..."`,
+/// and `link = "..."` passed to it, so it behaves exactly like calling that macro directly. It
+/// can be easier to read when there are many arguments.
+///
+/// # Example
+///
+/// ```ignore
+/// todo_or_die::check! {
+///     when: issue_closed("rust-lang/rust#44265"),
+///     message: "use generic associated types in `Collection`",
+///     owner: "@alice",
+///     link: "JIRA-42",
+/// }
+/// ```
+#[proc_macro]
+pub fn check(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    match syn::parse::<spec::CheckSpec>(input) {
+        Ok(spec) => spec.expand().into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Trigger a compile error if all of the given checks would.
+///
+/// The checks are written like the macros performing them, without the `!`. This is useful for
+/// waiting on several things, such as a fix being merged _and_ released:
+///
+/// ```ignore
+/// todo_or_die::all!(
+///     issue_closed("rust-lang/rust#44265"),
+///     crates_io("serde", ">=2"),
+/// );
+/// ```
+///
+/// Checks can be nested with `all(...)`, `any(...)`, and `not(...)`. All of them are performed,
+/// so if one fails because of a network error the result can still be decided by the others: the
+/// build isn't failed if any check passes. Warnings from the checks are emitted as usual.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::all!(after_date(1994, 10, 22), before_date(3000, 1, 1));
+/// ```
+#[proc_macro]
+pub fn all(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, combinators::all)
+}
+
+/// Trigger a compile error if any of the given checks would.
+///
+/// The checks are written the same way as for [`all!`]. All of them are performed, so if one fails
+/// because of a network error the build still fails if any of the others trigger an error.
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::any!(after_date(3000, 1, 1), after_date(1994, 10, 22));
+/// ```
+#[proc_macro]
+pub fn any(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, combinators::any)
+}
+
+/// Trigger a compile error if the given check wouldn't.
+///
+/// The check is written the same way as for [`all!`].
+///
+/// # Example
+///
+/// ```compile_fail
+/// todo_or_die::not!(after_date(3000, 1, 1));
+/// ```
+#[proc_macro]
+pub fn not(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    perform_check(input, combinators::not)
+}
+
+/// Trigger a compile error on an item if the given check would.
+///
+/// The check is written the same way as for [`all!`], so any check can be used. The item is
+/// passed through unchanged and the compile error names it, and points at its name, which keeps
+/// a workaround and the reason for removing it together:
+///
+/// ```compile_fail
+/// #[todo_or_die::when(after_date(1994, 10, 22))]
+/// fn retry_shim() {}
+/// ```
+///
+/// Or to remove a workaround once an upstream issue or pull request is closed:
+///
+/// ```ignore
+/// #[todo_or_die::when(issue_closed("tokio-rs/axum#1"))]
+/// fn workaround() {}
+///
+/// #[todo_or_die::when(pr_closed("tokio-rs/axum#266"))]
+/// impl Clone for Config {
+///     // ...
+/// }
+/// ```
+///
+/// This works on functions, modules, impl blocks, and other items. Rust doesn't allow attribute
+/// macros on struct fields, so instead put `#[todo_or_die::when]` on the struct and `#[when(...)]`
+/// on its fields:
+///
+/// ```compile_fail
+/// #[todo_or_die::when]
+/// struct Config {
+///     #[when(after_date(1994, 10, 22))]
+///     legacy_timeout: u32,
+/// }
+/// ```
+///
+/// Modifiers, such as `message = "..."`, go after the check like for other macros.
+#[proc_macro_attribute]
+pub fn when(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    item::when(attr.into(), item.into()).into()
+}
+
+/// Mark an item as `#[deprecated]` once the given check would trigger a compile error.
+///
+/// This is useful for sunsetting an API on a schedule. The check is written the same way as for
+/// [`all!`], with `date(...)` being short for `after_date(...)`:
+///
+/// ```ignore
+/// #[todo_or_die::deprecate_after(date(2025, 9, 1), note = "use new_api instead")]
+/// pub fn old_api() {}
+/// ```
+///
+/// From 2025-09-01 using `old_api` gives a deprecation warning. To also trigger a compile error
+/// once it's time to remove the item, pass a second, later check as `error_after`:
+///
+/// ```ignore
+/// #[todo_or_die::deprecate_after(
+///     date(2025, 9, 1),
+///     note = "use new_api instead",
+///     error_after = date(2026, 1, 1),
+/// )]
+/// pub fn old_api() {}
+/// ```
+///
+/// Requires the `time` feature to be enabled for `date(...)`.
+#[proc_macro_attribute]
+pub fn deprecate_after(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    item::deprecate_after(attr.into(), item.into()).into()
+}
+
+/// Trigger a compile error if a Cargo feature is enabled after the given check would trigger.
+///
+/// This enforces cleaning up feature flags, such as a feature kept around for a transition
+/// period. The check is written the same way as for [`all!`] and the item is passed through
+/// unchanged:
+///
+/// ```ignore
+/// #[todo_or_die::remove_feature("legacy-api", after = date(2026, 3, 1))]
+/// #[cfg(feature = "legacy-api")]
+/// mod legacy {
+///     // ...
+/// }
+/// ```
+///
+/// From 2026-03-01 compiling with the `legacy-api` feature enabled fails, while compiling
+/// without it still works.
+#[proc_macro_attribute]
+pub fn remove_feature(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    item::remove_feature(attr.into(), item.into()).into()
+}
+
+/// Trigger a compile error pointing at some code if the given check would.
+///
+/// The code is passed through unchanged, so the compile error lands exactly on the code that
+/// should be deleted. The check is written the same way as for [`all!`], followed by `;`:
+///
+/// ```ignore
+/// todo_or_die::remove_code! {
+///     when: issue_closed("tokio-rs/axum#1");
+///
+///     fn workaround() {
+///         // ...
+///     }
+/// }
+/// ```
+///
+/// The code can be items or statements.
+#[proc_macro]
+pub fn remove_code(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    block::remove_code(input.into()).into()
+}
+
+/// Compile some code until the given check would trigger a compile error, and then omit it.
+///
+/// This is useful for fallbacks that are only needed for a while. Rather than failing the
+/// build the code disappears with a warning, as a reminder to delete it. The check is written
+/// the same way as for [`all!`], followed by `;`:
+///
+/// ```ignore
+/// todo_or_die::keep_until! {
+///     date(2026, 1, 1);
+///
+///     impl From<LegacyConfig> for Config {
+///         // ...
+///     }
+/// }
+/// ```
+///
+/// The code can be items or statements, but nothing else may depend on it since it'll
+/// eventually be gone.
+#[proc_macro]
+pub fn keep_until(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    block::keep_until(input.into()).into()
+}
+
+/// Like [`std::todo!`] but triggers a compile error once the given check would.
+///
+/// Until then it panics at runtime like `todo!()` does, so it's a drop-in replacement for
+/// placeholders that must not be forgotten. The check is written the same way as for [`all!`]
+/// and is followed by the arguments to `todo!`:
+///
+/// ```ignore
+/// fn paginate(&self) -> Page {
+///     todo_or_die::todo_until!(date(2025, 12, 1), "implement pagination")
+/// }
+/// ```
+#[proc_macro]
+pub fn todo_until(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    block::todo_until(input.into()).into()
+}
+
+/// Like [`std::unimplemented!`] but triggers a compile error once the given check would.
+///
+/// This works the same way as [`todo_until!`]. It's useful for stubbed trait methods waiting on
+/// an upstream capability, such as a crate being released:
+///
+/// ```ignore
+/// impl Storage for S3 {
+///     fn compact(&self) {
+///         todo_or_die::unimplemented_until!(
+///             crates_io("aws-sdk-s3", ">=2"),
+///             "compaction needs aws-sdk-s3 2.0",
+///         )
+///     }
+/// }
+/// ```
+#[proc_macro]
+pub fn unimplemented_until(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    block::unimplemented_until(input.into()).into()
+}
+
+/// Define a temporary implementation of a function that triggers a compile error, pointing at
+/// the function, once the given check would.
+///
+/// This keeps a stub and the reason it's a stub together. The check is written the same way as
+/// for [`all!`], followed by `;` and the function:
+///
+/// ```ignore
+/// todo_or_die::stub_fn! {
+///     when: crates_io("upstream", ">=2.0");
+///
+///     fn fast_path(x: u32) -> u32 {
+///         slow_path(x)
+///     }
+/// }
+/// ```
+#[proc_macro]
+pub fn stub_fn(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    block::stub_fn(input.into()).into()
+}
+
+/// What a check decided. Most checks return `Option<String>`, where `Some` is a compile error.
+#[derive(Clone)]
+enum Outcome {
+    Pass,
+    /// Emit a warning but let the build succeed.
+    Warn(String),
+    Die(String),
+}
+
+impl From<Option<String>> for Outcome {
+    fn from(msg: Option<String>) -> Self {
+        match msg {
+            Some(msg) => Outcome::Die(msg),
+            None => Outcome::Pass,
+        }
+    }
+}
+
+#[allow(dead_code)]
+fn perform_check<F, T, R>(input: proc_macro::TokenStream, f: F) -> proc_macro::TokenStream
+where
+    F: FnOnce(T) -> anyhow::Result<R>,
+    T: syn::parse::Parse,
+    R: Into<Outcome>,
+{
+    perform_check_with(input, |input, _| f(input))
+}
+
+/// Like [`perform_check`] but for checks that don't make network requests, so `grace_days`
+/// isn't supported.
+#[allow(dead_code)]
+fn perform_local_check<F, T, R>(input: proc_macro::TokenStream, f: F) -> proc_macro::TokenStream
+where
+    F: FnOnce(T) -> anyhow::Result<R>,
+    T: syn::parse::Parse,
+    R: Into<Outcome>,
+{
+    perform_check_with(input, |input, modifiers| {
+        if let Some((_, span)) = modifiers.grace_days {
+            return Err(syn::Error::new(
+                span,
+                "`grace_days` is only supported by macros that make network requests",
+            )
+            .into());
+        }
+        f(input).map(Into::into)
+    })
+}
+
+/// Like [`perform_check`] but the check also gets the modifiers, which it can adjust, such as
+/// `remove_feature` only failing when a feature is enabled.
+#[allow(dead_code)]
+fn perform_check_with<F, T, R>(input: proc_macro::TokenStream, f: F) -> proc_macro::TokenStream
+where
+    F: FnOnce(T, &mut modifiers::Modifiers) -> anyhow::Result<R>,
+    T: syn::parse::Parse,
+    R: Into<Outcome>,
+{
+    perform_check_at(input, proc_macro2::Span::call_site(), f)
+}
+
+/// Like [`perform_check_with`] but the compile error or warning points at `span`, rather than the
+/// macro call.
+fn perform_check_at<F, T, R>(
+    input: proc_macro::TokenStream,
+    span: proc_macro2::Span,
+    f: F,
+) -> proc_macro::TokenStream
+where
+    F: FnOnce(T, &mut modifiers::Modifiers) -> anyhow::Result<R>,
+    T: syn::parse::Parse,
+    R: Into<Outcome>,
+{
+    if skip_checks() {
+        return Default::default();
+    }
+
+    let (input, mut modifiers) = match modifiers::Modifiers::split(input.into()) {
+        Ok(value) => value,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    // checks with an `id` keep their grace period when the code around them changes
+    #[cfg(feature = "__internal_http")]
+    let grace_key = match &modifiers.id {
+        Some(id) => grace::Key::Id(id.clone()),
+        None => grace::Key::Input(format!("{}:{}", std::any::type_name::<F>(), input)),
+    };
+
+    let input = match syn::parse2::<T>(input) {
+        Ok(value) => value,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    if modifiers.skip() {
+        return Default::default();
+    }
+
+    #[cfg(any(feature = "time", feature = "__internal_http"))]
+    if let Some(id) = &modifiers.id {
+        match snooze::is_snoozed(id) {
+            Ok(true) => return Default::default(),
+            Ok(false) => {}
+            Err(err) => {
+                let msg = err.to_string();
+                return quote::quote! {
+                    ::std::compile_error!(#msg);
+                }
+                .into();
+            }
+        }
+    }
+
+    let outcome = f(input, &mut modifiers).map(Into::into);
+
+    #[cfg(feature = "__internal_http")]
+    let outcome = outcome.map(|outcome| match modifiers.grace_days {
+        Some((days, _)) => grace::apply(&grace_key, days, outcome.clone()).unwrap_or_else(|err| {
+            eprintln!("something went wrong\n\n{:?}", err);
+            outcome
+        }),
+        None => outcome,
+    });
+
+    let outcome = outcome.map(|outcome| match outcome {
+        Outcome::Die(msg) if modifiers.warn => Outcome::Warn(msg),
+        outcome => outcome,
+    });
+
+    let mut tokens = take_warnings()
+        .iter()
+        .map(|msg| warning(msg, span))
+        .collect::<proc_macro2::TokenStream>();
+    tokens.extend(expand_outcome(outcome, &modifiers, span));
+    tokens.into()
+}
+
+/// The tokens a check expands to, given what it decided.
+fn expand_outcome(
+    outcome: anyhow::Result<Outcome>,
+    modifiers: &modifiers::Modifiers,
+    span: proc_macro2::Span,
+) -> proc_macro2::TokenStream {
+    match outcome {
+        Ok(Outcome::Pass) => {}
+        Ok(Outcome::Warn(msg)) => {
+            // this is also used with `nightly-diagnostics` since, unlike diagnostics, the
+            // warning respects lint levels such as `-D warnings`
+            return modifiers.wrap_error(warning(&modifiers.annotate(msg), span));
+        }
+        Ok(Outcome::Die(msg)) => {
+            #[cfg(todo_or_die_nightly_diagnostics)]
+            if !modifiers.is_conditional() {
+                diagnostic::emit(&msg, modifiers, span.unwrap());
+                return Default::default();
+            }
+
+            let msg = modifiers.annotate(msg);
+            return modifiers.wrap_error(quote::quote_spanned! {span=>
+                ::std::compile_error!(#msg);
+            });
+        }
+        Err(err) if is_fatal(&err) => {
+            if let Some(err) = err.downcast_ref::<syn::Error>() {
+                return err.to_compile_error();
+            }
+            let msg = err.to_string();
+            return quote::quote! {
+                ::std::compile_error!(#msg);
+            };
+        }
+        #[cfg(feature = "__internal_http")]
+        Err(err) if err.is::<http::Offline>() => {}
+        Err(err) => {
+            #[cfg(todo_or_die_nightly_diagnostics)]
+            diagnostic::emit_error(&err);
+            #[cfg(not(todo_or_die_nightly_diagnostics))]
+            eprintln!("something went wrong\n\n{:?}", err);
+        }
+    }
+
+    Default::default()
+}
+
+/// A rustc warning with the given message, pointing at `span`.
+fn warning(msg: &str, span: proc_macro2::Span) -> proc_macro2::TokenStream {
+    // there is no stable way for proc macros to emit warnings, but using a deprecated item does
+    // the trick
+    quote::quote_spanned! {span=>
+        const _: () = {
+            #[deprecated(note = #msg)]
+            struct TodoOrDie;
+            let _ = TodoOrDie;
+        };
+    }
+}
+
+thread_local! {
+    /// Warnings about the check being performed, that aren't part of what it decided.
+    static WARNINGS: std::cell::RefCell<Vec<String>> = const { std::cell::RefCell::new(Vec::new()) };
+}
+
+/// Emit a warning along with the outcome of the check being performed, such as a GitHub
+/// repository having moved.
+fn warn(msg: String) {
+    WARNINGS.with(|warnings| warnings.borrow_mut().push(msg));
+}
+
+fn take_warnings() -> Vec<String> {
+    WARNINGS.with(|warnings| warnings.take())
+}
+
+/// Whether all checks should be skipped because of the environment we're being compiled in.
+fn skip_checks() -> bool {
+    // docs.rs builds without network access and there is nothing to act on there anyway
+    if std::env::var_os("TODO_OR_DIE_SKIP").is_some() || std::env::var_os("DOCS_RS").is_some() {
+        return true;
+    }
+
+    is_env_flag_set("TODO_OR_DIE_ONLY_CI") && !is_ci()
+}
+
+/// Whether we're running on CI, based on the environment variables common CI providers set.
+fn is_ci() -> bool {
+    [
+        "CI",
+        "GITHUB_ACTIONS",
+        "GITLAB_CI",
+        "BUILDKITE",
+        "CIRCLECI",
+        "TRAVIS",
+        "JENKINS_URL",
+        "TF_BUILD",
+        "TEAMCITY_VERSION",
+    ]
+    .iter()
+    .any(|var| is_env_flag_set(var))
+}
+
+/// Whether an environment variable is set to something other than an empty string, `0`, or
+/// `false`, so `CI=false` works as expected.
+fn is_env_flag_set(var: &str) -> bool {
+    match std::env::var_os(var) {
+        Some(value) => {
+            let value = value.to_string_lossy();
+            let value = value.trim();
+            !(value.is_empty() || value == "0" || value.eq_ignore_ascii_case("false"))
+        }
+        None => false,
+    }
+}
+
+/// Whether an error means the macro input is definitely wrong, rather than something like the
+/// network being down.
+#[allow(dead_code)]
+fn is_fatal(err: &anyhow::Error) -> bool {
+    if err.downcast_ref::<syn::Error>().is_some() {
+        return true;
+    }
+
+    #[cfg(any(feature = "time", feature = "__internal_http"))]
+    {
+        if err.downcast_ref::<config::ConfigError>().is_some() {
+            return true;
+        }
+    }
+
+    #[cfg(feature = "__internal_package")]
+    {
+        if err.downcast_ref::<package::NotInRegistry>().is_some() {
+            return true;
+        }
+    }
+
+    #[cfg(feature = "__internal_http")]
+    {
+        err.downcast_ref::<http::NotFound>()
+            .is_some_and(|err| err.fatal)
+    }
+
+    #[cfg(not(feature = "__internal_http"))]
+    {
+        let _ = err;
+        false
+    }
 }
@@ -0,0 +1,424 @@
+use anyhow::{Context as _, Result};
+use semver::{Version, VersionReq};
+use serde::Deserialize;
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+    time::{Duration, SystemTime},
+};
+use syn::parse::Parse;
+
+pub(crate) fn dependency_version(input: Input) -> Result<Option<String>> {
+    let lockfile = Lockfile::read()?;
+
+    let versions = lockfile
+        .package
+        .iter()
+        .filter(|package| package.name == input.krate)
+        .map(|package| {
+            package
+                .version
+                .parse::<Version>()
+                .with_context(|| format!("Couldn't parse version of {}", package.name))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    if versions.is_empty() {
+        anyhow::bail!("{} isn't in {}", input.krate, lockfile.path.display());
+    }
+
+    // if several versions are locked, for example because of semver incompatible versions, wait
+    // until all of them match
+    if versions
+        .iter()
+        .all(|version| input.version_req.matches(version))
+    {
+        let versions = versions
+            .iter()
+            .map(|version| version.to_string())
+            .collect::<Vec<_>>();
+
+        Ok(Some(format!(
+            "{} is locked at {}. Time to act on this!",
+            input.krate,
+            versions.join(", ")
+        )))
+    } else {
+        Ok(None)
+    }
+}
+
+pub(crate) fn dependency_gone(input: CrateName) -> Result<Option<String>> {
+    let lockfile = Lockfile::read()?;
+
+    if lockfile
+        .package
+        .iter()
+        .any(|package| package.name == input.krate)
+    {
+        Ok(None)
+    } else {
+        Ok(Some(format!(
+            "{} is no longer in {}. Time to act on this!",
+            input.krate,
+            lockfile.path.display()
+        )))
+    }
+}
+
+pub(crate) fn lockfile_age(input: LockfileAge) -> Result<Option<String>> {
+    let path = lockfile_path()?;
+
+    let modified_at = match last_committed_at(&path) {
+        Some(committed_at) => committed_at,
+        None => std::fs::metadata(&path)
+            .and_then(|metadata| metadata.modified())
+            .with_context(|| format!("Failed to get modification time of {}", path.display()))?,
+    };
+
+    let days = SystemTime::from(crate::clock::now()?)
+        .duration_since(modified_at)
+        .unwrap_or_default()
+        .as_secs()
+        / (60 * 60 * 24);
+
+    if days >= input.days {
+        Ok(Some(format!(
+            "{} hasn't been updated in {} days. Time to act on this!",
+            path.display(),
+            days
+        )))
+    } else {
+        Ok(None)
+    }
+}
+
+/// When `path` was last committed, if it's tracked by git.
+fn last_committed_at(path: &Path) -> Option<SystemTime> {
+    let output = Command::new("git")
+        .arg("log")
+        .arg("-1")
+        .arg("--format=%ct")
+        .arg("--")
+        .arg(path.file_name()?)
+        .current_dir(path.parent()?)
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let secs = String::from_utf8(output.stdout).ok()?.trim().parse().ok()?;
+    Some(SystemTime::UNIX_EPOCH + Duration::from_secs(secs))
+}
+
+#[cfg(feature = "crate")]
+pub(crate) fn deps_outdated(input: DepsOutdated) -> Result<Option<String>> {
+    let lockfile = Lockfile::read()?;
+    let manifest = Manifest::read()?;
+
+    let mut outdated = Vec::new();
+    for name in manifest.crates_io_dependencies() {
+        let locked = match lockfile
+            .package
+            .iter()
+            .filter(|package| package.name == name && package.is_from_crates_io())
+            .filter_map(|package| package.version.parse::<Version>().ok())
+            .max()
+        {
+            Some(locked) => locked,
+            None => continue,
+        };
+
+        let latest = crate::krate::latest_crates_io_version(&name)?;
+
+        let is_outdated = if input.major_only {
+            !is_semver_compatible(&locked, &latest) && latest > locked
+        } else {
+            latest > locked
+        };
+
+        if is_outdated {
+            outdated.push(format!("{} {} -> {}", name, locked, latest));
+        }
+    }
+
+    if outdated.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(format!(
+            "Dependencies are outdated: {}. Time to act on this!",
+            outdated.join(", ")
+        )))
+    }
+}
+
+/// Whether Cargo considers `a` and `b` compatible, meaning the left-most non-zero component is
+/// the same.
+#[cfg(feature = "crate")]
+fn is_semver_compatible(a: &Version, b: &Version) -> bool {
+    if a.major != 0 || b.major != 0 {
+        a.major == b.major
+    } else if a.minor != 0 || b.minor != 0 {
+        a.minor == b.minor
+    } else {
+        a.patch == b.patch
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Lockfile {
+    #[serde(skip)]
+    path: PathBuf,
+    #[serde(default)]
+    package: Vec<Package>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Package {
+    name: String,
+    version: String,
+    #[cfg(feature = "crate")]
+    source: Option<String>,
+}
+
+impl Package {
+    #[cfg(feature = "crate")]
+    fn is_from_crates_io(&self) -> bool {
+        matches!(
+            self.source.as_deref(),
+            Some(
+                "registry+https://github.com/rust-lang/crates.io-index"
+                    | "sparse+https://index.crates.io/"
+            )
+        )
+    }
+}
+
+impl Lockfile {
+    /// Read the `Cargo.lock` of the crate being compiled.
+    fn read() -> Result<Self> {
+        let path = lockfile_path()?;
+
+        let contents = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+
+        let mut lockfile = toml::from_str::<Self>(&contents)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+        lockfile.path = path;
+
+        Ok(lockfile)
+    }
+}
+
+/// The parts of the `Cargo.toml` of the crate being compiled that we care about.
+#[cfg(feature = "crate")]
+#[derive(Debug, Deserialize)]
+struct Manifest {
+    #[serde(default)]
+    dependencies: std::collections::BTreeMap<String, toml::Value>,
+    #[serde(default, rename = "dev-dependencies")]
+    dev_dependencies: std::collections::BTreeMap<String, toml::Value>,
+    #[serde(default, rename = "build-dependencies")]
+    build_dependencies: std::collections::BTreeMap<String, toml::Value>,
+}
+
+#[cfg(feature = "crate")]
+impl Manifest {
+    fn read() -> Result<Self> {
+        let manifest_dir =
+            std::env::var("CARGO_MANIFEST_DIR").context("`CARGO_MANIFEST_DIR` isn't set")?;
+        let path = Path::new(&manifest_dir).join("Cargo.toml");
+
+        let contents = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+
+        toml::from_str(&contents).with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    /// The names of the direct dependencies that come from crates.io, with renames resolved.
+    ///
+    /// Path, git, and alternative registry dependencies are skipped.
+    fn crates_io_dependencies(&self) -> std::collections::BTreeSet<String> {
+        self.dependencies
+            .iter()
+            .chain(&self.dev_dependencies)
+            .chain(&self.build_dependencies)
+            .filter_map(|(key, value)| {
+                let table = match value {
+                    toml::Value::String(_) => return Some(key.clone()),
+                    toml::Value::Table(table) => table,
+                    _ => return None,
+                };
+
+                if ["path", "git", "registry"]
+                    .iter()
+                    .any(|source| table.contains_key(*source))
+                {
+                    return None;
+                }
+
+                let name = table
+                    .get("package")
+                    .and_then(|package| package.as_str())
+                    .unwrap_or(key);
+                Some(name.to_owned())
+            })
+            .collect()
+    }
+}
+
+/// Find `Cargo.lock` by looking in `CARGO_MANIFEST_DIR` and its ancestors, since in a workspace
+/// the lockfile is next to the workspace's `Cargo.toml` rather than the crate's.
+fn lockfile_path() -> Result<PathBuf> {
+    let manifest_dir =
+        std::env::var("CARGO_MANIFEST_DIR").context("`CARGO_MANIFEST_DIR` isn't set")?;
+
+    Path::new(&manifest_dir)
+        .ancestors()
+        .map(|dir| dir.join("Cargo.lock"))
+        .find(|path| path.is_file())
+        .with_context(|| {
+            format!(
+                "Couldn't find Cargo.lock in {} or its parents",
+                manifest_dir
+            )
+        })
+}
+
+pub(crate) struct Input {
+    krate: String,
+    version_req: VersionReq,
+}
+
+impl Parse for Input {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let krate = input.parse::<syn::LitStr>()?.value();
+
+        input.parse::<syn::token::Comma>()?;
+
+        let lit = input.parse::<syn::LitStr>()?;
+        let version_req = lit
+            .value()
+            .parse()
+            .map_err(|err| syn::Error::new(lit.span(), err))?;
+
+        input.parse::<syn::token::Comma>().ok();
+
+        Ok(Self { krate, version_req })
+    }
+}
+
+pub(crate) struct CrateName {
+    krate: String,
+}
+
+impl Parse for CrateName {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let krate = input.parse::<syn::LitStr>()?.value();
+
+        input.parse::<syn::token::Comma>().ok();
+
+        Ok(Self { krate })
+    }
+}
+
+pub(crate) struct LockfileAge {
+    days: u64,
+}
+
+impl Parse for LockfileAge {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let ident = input.parse::<syn::Ident>()?;
+        if ident != "days" {
+            return Err(syn::Error::new(ident.span(), "expected `days`"));
+        }
+        input.parse::<syn::token::Eq>()?;
+        let days = input.parse::<syn::LitInt>()?.base10_parse()?;
+
+        input.parse::<syn::token::Comma>().ok();
+
+        Ok(Self { days })
+    }
+}
+
+#[cfg(feature = "crate")]
+pub(crate) struct DepsOutdated {
+    major_only: bool,
+}
+
+#[cfg(feature = "crate")]
+impl Parse for DepsOutdated {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut major_only = false;
+
+        if !input.is_empty() {
+            let ident = input.parse::<syn::Ident>()?;
+            if ident != "major_only" {
+                return Err(syn::Error::new(ident.span(), "expected `major_only`"));
+            }
+            major_only = true;
+
+            input.parse::<syn::token::Comma>().ok();
+        }
+
+        Ok(Self { major_only })
+    }
+}
+
+/// # `dependency_version`
+///
+/// ```compile_fail
+/// todo_or_die::dependency_version!("anyhow", ">=1.0");
+/// ```
+///
+/// ```
+/// todo_or_die::dependency_version!("anyhow", ">=1000.0");
+/// ```
+///
+/// several versions locked and not all of them match
+/// ```
+/// todo_or_die::dependency_version!("syn", ">=2.0");
+/// ```
+///
+/// # `dependency_gone`
+///
+/// dependency that's been removed
+/// ```compile_fail
+/// todo_or_die::dependency_gone!("this-crate-is-not-a-dependency");
+/// ```
+///
+/// dependency that's still there
+/// ```
+/// todo_or_die::dependency_gone!("anyhow");
+/// ```
+///
+/// # `lockfile_age`
+///
+/// ```compile_fail
+/// todo_or_die::lockfile_age!(days = 0);
+/// ```
+///
+/// ```
+/// todo_or_die::lockfile_age!(days = 100000);
+/// ```
+///
+/// missing `days`
+/// ```compile_fail
+/// todo_or_die::lockfile_age!(90);
+/// ```
+///
+/// # `deps_outdated`
+///
+/// depends on an old major version of syn
+/// ```compile_fail
+/// todo_or_die::deps_outdated!(major_only);
+/// ```
+///
+/// unknown argument
+/// ```compile_fail
+/// todo_or_die::deps_outdated!(minor_only);
+/// ```
+#[allow(dead_code)]
+fn tests() {}
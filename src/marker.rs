@@ -0,0 +1,102 @@
+use anyhow::{Context as _, Result};
+use std::path::PathBuf;
+
+/// The file, relative to the crate root, where checks record state that should be committed so
+/// it's shared by everyone building the crate, including CI. Can be changed with
+/// `TODO_OR_DIE_FILE`.
+///
+/// Each line is `key = value`. `dies_in!` uses the check's `id` as the key.
+const FILE: &str = ".todo-or-die";
+
+/// The value recorded for `key`, if any.
+pub(crate) fn get(key: &str) -> Result<Option<String>> {
+    let path = path()?;
+    let contents = match std::fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err).with_context(|| format!("Failed to read {}", path.display())),
+    };
+
+    Ok(contents
+        .lines()
+        .filter_map(parse_line)
+        .find(|(line_key, _)| *line_key == key)
+        .map(|(_, value)| value.to_string()))
+}
+
+/// Record `value` for `key`, which mustn't already be recorded.
+pub(crate) fn insert(key: &str, value: &str) -> Result<()> {
+    use std::io::Write as _;
+
+    if !is_writable() {
+        return Ok(());
+    }
+
+    let path = path()?;
+    let is_new = !path.exists();
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+
+    if is_new {
+        writeln!(
+            file,
+            "# Recorded by `todo_or_die`. Commit this file so everyone shares the same deadlines."
+        )?;
+    }
+    writeln!(file, "{} = {}", key, value)?;
+
+    Ok(())
+}
+
+/// Remove the value recorded for `key`, if any.
+#[cfg_attr(not(feature = "__internal_http"), allow(dead_code))]
+pub(crate) fn remove(key: &str) -> Result<()> {
+    if !is_writable() {
+        return Ok(());
+    }
+
+    let path = path()?;
+    let contents = match std::fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err).with_context(|| format!("Failed to read {}", path.display())),
+    };
+
+    let is_key = |line: &str| parse_line(line).is_some_and(|(line_key, _)| line_key == key);
+    if !contents.lines().any(is_key) {
+        return Ok(());
+    }
+
+    let contents = contents
+        .lines()
+        .filter(|line| !is_key(line))
+        .map(|line| format!("{}\n", line))
+        .collect::<String>();
+    std::fs::write(&path, contents).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+fn path() -> Result<PathBuf> {
+    let dir = std::env::var("CARGO_MANIFEST_DIR").context("`CARGO_MANIFEST_DIR` isn't set")?;
+    let file = std::env::var_os("TODO_OR_DIE_FILE").unwrap_or_else(|| FILE.into());
+    // an absolute path replaces `dir`
+    Ok(PathBuf::from(dir).join(file))
+}
+
+/// Only the crates being built by the user are updated, and not dependencies whose files live in
+/// cargo's registry or git checkouts.
+fn is_writable() -> bool {
+    std::env::var_os("CARGO_PRIMARY_PACKAGE").is_some()
+}
+
+fn parse_line(line: &str) -> Option<(&str, &str)> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+
+    let mut parts = line.splitn(2, '=').map(str::trim);
+    Some((parts.next()?, parts.next().unwrap_or_default()))
+}
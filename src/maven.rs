@@ -0,0 +1,94 @@
+use crate::{http::request, package};
+use anyhow::{Context as _, Result};
+use hyper::Request;
+use serde::Deserialize;
+use syn::parse::Parse;
+
+pub(crate) fn maven(input: Input) -> Result<Option<String>> {
+    #[derive(Debug, Deserialize)]
+    struct Response {
+        response: Docs,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Docs {
+        docs: Vec<Doc>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Doc {
+        v: String,
+    }
+
+    let data = request::<Response>(
+        Request::builder()
+            .uri(format!(
+                "https://search.maven.org/solrsearch/select?q=g:%22{}%22+AND+a:%22{}%22&core=gav&rows=100&wt=json",
+                input.group, input.artifact
+            ))
+            .body(())
+            .unwrap(),
+    )?;
+
+    // the search responds with no results for artifacts that don't exist
+    if data.response.docs.is_empty() {
+        return Err(package::NotInRegistry {
+            name: input.package.name,
+            registry: "Maven Central".to_owned(),
+        }
+        .into());
+    }
+
+    let latest_version = package::latest_stable(data.response.docs.iter().map(|doc| &doc.v))
+        .context("No versions found for artifact")?;
+
+    Ok(package::check(
+        &input.package,
+        "Maven Central",
+        &latest_version,
+    ))
+}
+
+pub(crate) struct Input {
+    group: String,
+    artifact: String,
+    package: package::Input,
+}
+
+impl Parse for Input {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let lit = input.fork().parse::<syn::LitStr>()?;
+        let package = input.parse::<package::Input>()?;
+
+        let (group, artifact) = package
+            .name
+            .split_once(':')
+            .filter(|(group, artifact)| !group.is_empty() && !artifact.is_empty())
+            .ok_or_else(|| syn::Error::new(lit.span(), "expected `group:artifact`"))?;
+
+        Ok(Self {
+            group: group.to_owned(),
+            artifact: artifact.to_owned(),
+            package,
+        })
+    }
+}
+
+/// ```compile_fail
+/// todo_or_die::maven!("com.google.guava:guava", ">=30");
+/// ```
+///
+/// ```
+/// todo_or_die::maven!("com.google.guava:guava", ">=1000");
+/// ```
+///
+/// ```compile_fail
+/// todo_or_die::maven!("com.google.guava", ">=1000");
+/// ```
+///
+/// unknown artifact
+/// ```compile_fail
+/// todo_or_die::maven!("com.google.guava:no-such-artifact-todo-or-die", ">=0");
+/// ```
+#[allow(dead_code)]
+fn tests() {}
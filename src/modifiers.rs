@@ -0,0 +1,429 @@
+use proc_macro2::{TokenStream, TokenTree};
+
+/// Arguments accepted by every macro that change how the check is enforced, such as
+/// `only(target_os = "windows")`, or `warn:` at the start.
+#[derive(Default)]
+pub(crate) struct Modifiers {
+    /// A `cfg` predicate the compile error is conditional on.
+    pub(crate) only: Option<syn::Meta>,
+    /// Only enforce the check when building with this profile.
+    pub(crate) profile: Option<Profile>,
+    /// How many days to wait after the check first fails before failing the build, and where
+    /// that was given.
+    pub(crate) grace_days: Option<(u32, proc_macro2::Span)>,
+    /// What to do when the check fails, added to the compile error.
+    pub(crate) message: Option<String>,
+    /// Who to ask about the check, such as `@alice`, added to the compile error.
+    pub(crate) owner: Option<String>,
+    /// Where to read more, such as a ticket, added to the compile error.
+    pub(crate) link: Option<String>,
+    /// A stable identifier for the check, such as `retry-shim`.
+    pub(crate) id: Option<String>,
+    /// Emit a warning rather than a compile error when the check fails.
+    pub(crate) warn: bool,
+}
+
+/// About 100 years, which is plenty and keeps the grace period's deadline within what dates can
+/// represent.
+const MAX_GRACE_DAYS: u32 = 36525;
+
+#[derive(Clone, Copy)]
+pub(crate) enum Profile {
+    Release,
+    Debug,
+}
+
+impl Profile {
+    fn as_str(self) -> &'static str {
+        match self {
+            Profile::Release => "release",
+            Profile::Debug => "debug",
+        }
+    }
+}
+
+impl Modifiers {
+    /// Split modifiers off the start and end of the macro input, returning the remaining input.
+    pub(crate) fn split(input: TokenStream) -> syn::Result<(TokenStream, Self)> {
+        let mut tokens = input.into_iter().collect::<Vec<_>>();
+        let mut modifiers = Self::default();
+
+        // `after_date!(warn: (..), die: (..))` already uses `warn:` for its own purposes
+        let is_escalation = tokens
+            .windows(2)
+            .any(|pair| is_labeled(&pair[0], &pair[1], "die"));
+        if tokens.len() >= 2 && is_labeled(&tokens[0], &tokens[1], "warn") && !is_escalation {
+            modifiers.warn = true;
+            tokens.drain(..2);
+        }
+
+        loop {
+            let mut end = tokens.len();
+            if end > 0 && is_comma(&tokens[end - 1]) {
+                end -= 1;
+            }
+
+            let start = match &tokens[..end] {
+                [.., TokenTree::Ident(ident), TokenTree::Group(group)]
+                    if ident == "only"
+                        && group.delimiter() == proc_macro2::Delimiter::Parenthesis =>
+                {
+                    end - 2
+                }
+                [.., TokenTree::Ident(ident)]
+                    if ident == "only_release" || ident == "only_debug" =>
+                {
+                    end - 1
+                }
+                [.., TokenTree::Ident(ident), TokenTree::Punct(eq), TokenTree::Literal(_)]
+                    if is_assignment_modifier(ident) && eq.as_char() == '=' =>
+                {
+                    end - 3
+                }
+                _ => break,
+            };
+
+            if start > 0 && !is_comma(&tokens[start - 1]) {
+                break;
+            }
+
+            match &tokens[start..end] {
+                [TokenTree::Ident(ident), TokenTree::Group(group)] => {
+                    if modifiers.only.is_some() {
+                        return Err(syn::Error::new(ident.span(), "duplicate `only`"));
+                    }
+                    let predicate = syn::parse2::<syn::Meta>(group.stream()).map_err(|_| {
+                        syn::Error::new(
+                            group.span(),
+                            "expected a `cfg` predicate, such as `only(target_os = \"windows\")`",
+                        )
+                    })?;
+                    modifiers.only = Some(predicate);
+                }
+                [TokenTree::Ident(ident)] => {
+                    if modifiers.profile.is_some() {
+                        return Err(syn::Error::new(
+                            ident.span(),
+                            "only one of `only_release` or `only_debug` can be used",
+                        ));
+                    }
+                    modifiers.profile = Some(if ident == "only_release" {
+                        Profile::Release
+                    } else {
+                        Profile::Debug
+                    });
+                }
+                [TokenTree::Ident(ident), _, TokenTree::Literal(lit)] if ident == "grace_days" => {
+                    if cfg!(not(feature = "__internal_http")) {
+                        return Err(syn::Error::new(
+                            ident.span(),
+                            "`grace_days` is only supported by macros that make network requests",
+                        ));
+                    }
+                    if modifiers.grace_days.is_some() {
+                        return Err(syn::Error::new(ident.span(), "duplicate `grace_days`"));
+                    }
+                    let lit = syn::LitInt::from(lit.clone());
+                    let days = lit.base10_parse()?;
+                    if days > MAX_GRACE_DAYS {
+                        return Err(syn::Error::new(
+                            lit.span(),
+                            format!("`grace_days` must be at most {}", MAX_GRACE_DAYS),
+                        ));
+                    }
+                    modifiers.grace_days = Some((days, ident.span()));
+                }
+                [TokenTree::Ident(ident), _, TokenTree::Literal(lit)] => {
+                    let field = if ident == "message" {
+                        &mut modifiers.message
+                    } else if ident == "owner" {
+                        &mut modifiers.owner
+                    } else if ident == "link" {
+                        &mut modifiers.link
+                    } else {
+                        &mut modifiers.id
+                    };
+                    if field.is_some() {
+                        return Err(syn::Error::new(
+                            ident.span(),
+                            format!("duplicate `{}`", ident),
+                        ));
+                    }
+                    let value = syn::parse2::<syn::LitStr>(TokenTree::from(lit.clone()).into())
+                        .map_err(|_| syn::Error::new(lit.span(), "expected a string"))?;
+                    if ident == "id" && !is_valid_id(&value.value()) {
+                        return Err(syn::Error::new(
+                            lit.span(),
+                            "`id` must be non-empty and only contain letters, numbers, `-`, and `_`",
+                        ));
+                    }
+                    *field = Some(value.value());
+                }
+                _ => unreachable!(),
+            }
+
+            tokens.truncate(start.saturating_sub(1));
+        }
+
+        Ok((tokens.into_iter().collect(), modifiers))
+    }
+
+    /// Whether the check can be skipped entirely, without doing any work.
+    ///
+    /// That is the case if its `id` is listed in `TODO_OR_DIE_SKIP_IDS`, or for `only_release`
+    /// and `only_debug` if a build script has told us which profile is being built through
+    /// `TODO_OR_DIE_PROFILE`.
+    pub(crate) fn skip(&self) -> bool {
+        if let (Some(id), Ok(skipped)) = (&self.id, std::env::var("TODO_OR_DIE_SKIP_IDS")) {
+            if skipped.split(',').any(|skipped| skipped.trim() == id) {
+                return true;
+            }
+        }
+
+        match (self.profile, current_profile()) {
+            (Some(profile), Some(current)) => profile.as_str() != current,
+            _ => false,
+        }
+    }
+
+    /// Add the `id`, `message`, `owner`, and `link` to the message of a compile error or warning.
+    pub(crate) fn annotate(&self, mut msg: String) -> String {
+        if let Some(id) = &self.id {
+            msg = format!("[{}] {}", id, msg);
+        }
+        if let Some(message) = &self.message {
+            msg.push('\n');
+            msg.push_str(message);
+        }
+        if let Some(owner) = &self.owner {
+            msg.push_str("\nOwner: ");
+            msg.push_str(owner);
+        }
+        if let Some(link) = &self.link {
+            msg.push_str("\nLink: ");
+            msg.push_str(link);
+        }
+        msg
+    }
+
+    /// Whether the compile error or warning only applies to some targets or profiles, which
+    /// requires emitting it as tokens with `cfg` attributes.
+    pub(crate) fn is_conditional(&self) -> bool {
+        self.only.is_some() || (self.profile.is_some() && current_profile().is_none())
+    }
+
+    /// Apply the modifiers to the compile error or warning a check produced.
+    pub(crate) fn wrap_error(&self, error: TokenStream) -> TokenStream {
+        let only = self.only.as_ref().map(|predicate| {
+            quote::quote! { #[cfg(#predicate)] }
+        });
+
+        // without the build script handshake we fall back to `debug_assertions`, which is what
+        // distinguishes the default `dev` and `release` profiles
+        let profile = match (self.profile, current_profile()) {
+            (Some(Profile::Release), None) => Some(quote::quote! { #[cfg(not(debug_assertions))] }),
+            (Some(Profile::Debug), None) => Some(quote::quote! { #[cfg(debug_assertions)] }),
+            _ => None,
+        };
+
+        quote::quote! {
+            #only
+            #profile
+            #error
+        }
+    }
+}
+
+/// The profile being built, as set by a build script with
+/// `println!("cargo:rustc-env=TODO_OR_DIE_PROFILE={}", std::env::var("PROFILE").unwrap())`.
+fn current_profile() -> Option<String> {
+    std::env::var("TODO_OR_DIE_PROFILE").ok()
+}
+
+fn is_assignment_modifier(ident: &proc_macro2::Ident) -> bool {
+    ["grace_days", "message", "owner", "link", "id"]
+        .iter()
+        .any(|name| ident == name)
+}
+
+/// Whether `id` is a valid `id`, which is also used by `dies_in!`.
+pub(crate) fn is_valid_id(id: &str) -> bool {
+    !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Whether the tokens are `label:`.
+pub(crate) fn is_labeled(ident: &TokenTree, colon: &TokenTree, label: &str) -> bool {
+    matches!(ident, TokenTree::Ident(ident) if ident == label)
+        && matches!(
+            colon,
+            TokenTree::Punct(punct)
+                if punct.as_char() == ':' && punct.spacing() == proc_macro2::Spacing::Alone
+        )
+}
+
+fn is_comma(token: &TokenTree) -> bool {
+    matches!(token, TokenTree::Punct(punct) if punct.as_char() == ',')
+}
+
+/// ```compile_fail
+/// todo_or_die::after_date!(1990, 01, 01, only(not(target_os = "no-such-os")));
+/// ```
+///
+/// ```
+/// todo_or_die::after_date!(1990, 01, 01, only(target_os = "no-such-os"));
+/// ```
+///
+/// trailing comma
+/// ```
+/// todo_or_die::after_date!(1990, 01, 01, only(target_os = "no-such-os"),);
+/// ```
+///
+/// inside a function
+/// ```
+/// fn workaround() {
+///     todo_or_die::after_date!(1990, 01, 01, only(target_os = "no-such-os"));
+/// }
+/// ```
+///
+/// ```compile_fail
+/// fn workaround() {
+///     todo_or_die::after_date!(1990, 01, 01, only(not(target_os = "no-such-os")));
+/// }
+/// ```
+///
+/// invalid predicate
+/// ```compile_fail
+/// todo_or_die::after_date!(3000, 01, 01, only("windows"));
+/// ```
+///
+/// duplicate `only`
+/// ```compile_fail
+/// todo_or_die::after_date!(3000, 01, 01, only(unix), only(windows));
+/// ```
+///
+/// doctests are built with debug assertions
+/// ```
+/// todo_or_die::after_date!(1990, 01, 01, only_release);
+/// ```
+///
+/// ```compile_fail
+/// todo_or_die::after_date!(1990, 01, 01, only_debug);
+/// ```
+///
+/// combined with `only`
+/// ```compile_fail
+/// todo_or_die::after_date!(1990, 01, 01, only(not(target_os = "no-such-os")), only_debug);
+/// ```
+///
+/// ```
+/// todo_or_die::after_date!(1990, 01, 01, only_debug, only(target_os = "no-such-os"));
+/// ```
+///
+/// both profiles
+/// ```compile_fail
+/// todo_or_die::after_date!(3000, 01, 01, only_release, only_debug);
+/// ```
+///
+/// # `message`
+///
+/// ```compile_fail
+/// todo_or_die::after_date!(1990, 01, 01, message = "remove the retry shim in src/net.rs");
+/// ```
+///
+/// ```
+/// todo_or_die::after_date!(3000, 01, 01, "Europe/Stockholm", message = "remove the retry shim");
+/// ```
+///
+/// ```
+/// todo_or_die::after_date!(3000, 01, 01, message = "remove the retry shim", only_debug,);
+/// ```
+///
+/// duplicate `message`
+/// ```compile_fail
+/// todo_or_die::after_date!(3000, 01, 01, message = "a", message = "b");
+/// ```
+///
+/// ```compile_fail
+/// todo_or_die::after_date!(3000, 01, 01, message = 1);
+/// ```
+///
+/// # `owner`
+///
+/// ```compile_fail
+/// todo_or_die::after_date!(1990, 01, 01, owner = "@alice");
+/// ```
+///
+/// ```
+/// todo_or_die::after_date!(3000, 01, 01, message = "remove the retry shim", owner = "@alice");
+/// ```
+///
+/// ```
+/// todo_or_die::after_date!(3000, 01, 01, owner = "@alice", message = "remove the retry shim");
+/// ```
+///
+/// duplicate `owner`
+/// ```compile_fail
+/// todo_or_die::after_date!(3000, 01, 01, owner = "@alice", owner = "@bob");
+/// ```
+///
+/// # `id`
+///
+/// ```compile_fail
+/// todo_or_die::after_date!(1990, 01, 01, id = "retry-shim");
+/// ```
+///
+/// ```
+/// todo_or_die::after_date!(3000, 01, 01, id = "retry-shim", message = "remove the retry shim");
+/// ```
+///
+/// ```compile_fail
+/// todo_or_die::after_date!(3000, 01, 01, id = "retry shim");
+/// ```
+///
+/// ```compile_fail
+/// todo_or_die::after_date!(3000, 01, 01, id = "a", id = "b");
+/// ```
+///
+/// # `link`
+///
+/// ```compile_fail
+/// todo_or_die::after_date!(1990, 01, 01, link = "JIRA-42");
+/// ```
+///
+/// ```
+/// todo_or_die::after_date!(3000, 01, 01, link = "https://example.com/JIRA-42");
+/// ```
+///
+/// # `warn`
+///
+/// ```
+/// todo_or_die::after_date!(warn: 1990, 01, 01);
+/// ```
+///
+/// ```
+/// todo_or_die::after_date!(warn: 1990, 01, 01, message = "remove the retry shim", id = "a");
+/// ```
+///
+/// ```compile_fail
+/// #![deny(warnings)]
+/// todo_or_die::after_date!(warn: 1990, 01, 01);
+/// ```
+///
+/// ```
+/// todo_or_die::all!(warn: after_date(1990, 01, 01), not(after_date(3000, 01, 01)));
+/// ```
+///
+/// escalation still works
+/// ```compile_fail
+/// todo_or_die::after_date!(warn: (1990, 01, 01), die: (1991, 01, 01));
+/// ```
+///
+/// ```compile_fail
+/// todo_or_die::after_date!(warn:: 1990, 01, 01);
+/// ```
+#[cfg(feature = "time")]
+#[allow(dead_code)]
+fn tests() {}
@@ -0,0 +1,133 @@
+use crate::{http::request, package};
+use anyhow::{Context as _, Result};
+use hyper::Request;
+use serde::Deserialize;
+use syn::parse::Parse;
+
+pub(crate) fn nixpkgs(input: Input) -> Result<Option<String>> {
+    #[derive(Debug, Deserialize)]
+    struct Package {
+        repo: String,
+        version: String,
+    }
+
+    // repology tracks the versions in each channel and, unlike search.nixos.org, doesn't require
+    // credentials
+    let packages = request::<Vec<Package>>(
+        Request::builder()
+            .uri(format!(
+                "https://repology.org/api/v1/project/{}",
+                input.package.name
+            ))
+            .body(())
+            .unwrap(),
+    )?;
+
+    let repo = repology_repo(&input.channel).with_context(|| {
+        format!(
+            "Unsupported channel `{}`. Expected `nixos-unstable` or `nixos-YY.MM`",
+            input.channel
+        )
+    })?;
+
+    // repology responds with an empty list for projects it doesn't know about
+    if packages.is_empty() {
+        return Err(package::NotInRegistry {
+            name: input.package.name,
+            registry: "nixpkgs".to_owned(),
+        }
+        .into());
+    }
+
+    let latest_version = packages
+        .iter()
+        .filter(|package| package.repo == repo)
+        .filter_map(|package| package::parse_version(&package.version))
+        .max()
+        .with_context(|| format!("Package not found in {}", input.channel))?;
+
+    Ok(package::check(
+        &input.package,
+        &format!("nixpkgs ({})", input.channel),
+        &latest_version,
+    ))
+}
+
+/// Map a channel name such as `nixos-24.11` to repology's name for it, such as
+/// `nix_stable_24_11`.
+fn repology_repo(channel: &str) -> Option<String> {
+    match channel {
+        "nixos-unstable" | "nixpkgs-unstable" => Some("nix_unstable".to_owned()),
+        _ => {
+            let version = channel
+                .strip_prefix("nixos-")
+                .or_else(|| channel.strip_prefix("nixpkgs-"))?;
+            let (year, month) = version.split_once('.')?;
+            Some(format!("nix_stable_{}_{}", year, month))
+        }
+    }
+}
+
+pub(crate) struct Input {
+    channel: String,
+    package: package::Input,
+}
+
+impl Parse for Input {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let name = input.parse::<syn::LitStr>()?.value();
+        input.parse::<syn::token::Comma>()?;
+
+        let mut channel = "nixos-unstable".to_owned();
+        if input.peek(syn::Ident) {
+            let ident = input.parse::<syn::Ident>()?;
+            if ident != "channel" {
+                return Err(syn::Error::new(ident.span(), "expected `channel`"));
+            }
+            input.parse::<syn::token::Eq>()?;
+
+            let lit = input.parse::<syn::LitStr>()?;
+            if repology_repo(&lit.value()).is_none() {
+                return Err(syn::Error::new(
+                    lit.span(),
+                    "expected `nixos-unstable` or `nixos-YY.MM`",
+                ));
+            }
+            channel = lit.value();
+
+            input.parse::<syn::token::Comma>()?;
+        }
+
+        let lit = input.parse::<syn::LitStr>()?;
+        let version_req = lit
+            .value()
+            .parse()
+            .map_err(|err| syn::Error::new(lit.span(), err))?;
+
+        input.parse::<syn::token::Comma>().ok();
+
+        Ok(Self {
+            channel,
+            package: package::Input { name, version_req },
+        })
+    }
+}
+
+/// ```compile_fail
+/// todo_or_die::nixpkgs!("ripgrep", channel = "nixos-unstable", ">=13");
+/// ```
+///
+/// ```
+/// todo_or_die::nixpkgs!("ripgrep", ">=1000");
+/// ```
+///
+/// ```compile_fail
+/// todo_or_die::nixpkgs!("ripgrep", channel = "debian", ">=13");
+/// ```
+///
+/// unknown package
+/// ```compile_fail
+/// todo_or_die::nixpkgs!("no-such-package-todo-or-die", ">=0");
+/// ```
+#[allow(dead_code)]
+fn tests() {}
@@ -0,0 +1,41 @@
+use crate::{http::request, package};
+use anyhow::{Context as _, Result};
+use hyper::Request;
+use serde::Deserialize;
+
+pub(crate) fn npm(input: package::Input) -> Result<Option<String>> {
+    #[derive(Debug, Deserialize)]
+    struct DistTags {
+        latest: String,
+    }
+
+    let dist_tags = request::<DistTags>(
+        Request::builder()
+            .uri(format!(
+                "https://registry.npmjs.org/-/package/{}/dist-tags",
+                input.name.replace('/', "%2F")
+            ))
+            .body(())
+            .unwrap(),
+    )?;
+
+    let latest_version =
+        package::parse_version(&dist_tags.latest).context("Couldn't parse latest version")?;
+
+    Ok(package::check(&input, "npm", &latest_version))
+}
+
+/// ```compile_fail
+/// todo_or_die::npm!("react", ">=18");
+/// ```
+///
+/// ```
+/// todo_or_die::npm!("react", ">=1000");
+/// ```
+///
+/// scoped package
+/// ```compile_fail
+/// todo_or_die::npm!("@types/node", ">=20");
+/// ```
+#[allow(dead_code)]
+fn tests() {}
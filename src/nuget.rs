@@ -0,0 +1,36 @@
+use crate::{http::request, package};
+use anyhow::{Context as _, Result};
+use hyper::Request;
+use serde::Deserialize;
+
+pub(crate) fn nuget(input: package::Input) -> Result<Option<String>> {
+    #[derive(Debug, Deserialize)]
+    struct Response {
+        versions: Vec<String>,
+    }
+
+    let data = request::<Response>(
+        Request::builder()
+            .uri(format!(
+                "https://api.nuget.org/v3-flatcontainer/{}/index.json",
+                input.name.to_lowercase()
+            ))
+            .body(())
+            .unwrap(),
+    )?;
+
+    let latest_version =
+        package::latest_stable(&data.versions).context("No versions found for package")?;
+
+    Ok(package::check(&input, "NuGet", &latest_version))
+}
+
+/// ```compile_fail
+/// todo_or_die::nuget!("Newtonsoft.Json", ">=13");
+/// ```
+///
+/// ```
+/// todo_or_die::nuget!("Newtonsoft.Json", ">=1000");
+/// ```
+#[allow(dead_code)]
+fn tests() {}
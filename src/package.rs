@@ -0,0 +1,166 @@
+use semver::{BuildMetadata, Prerelease, Version, VersionReq};
+use std::fmt;
+use syn::parse::Parse;
+
+/// Check if the latest version of a package matches the version requirement.
+pub(crate) fn check(input: &Input, registry: &str, latest_version: &Version) -> Option<String> {
+    if input.version_req.matches(latest_version) {
+        Some(format!(
+            "Latest version of {} on {} is {}. Time to act on this!",
+            input.name, registry, latest_version
+        ))
+    } else {
+        None
+    }
+}
+
+/// Find the greatest version that isn't a pre-release.
+#[cfg(any(
+    feature = "git",
+    feature = "github",
+    feature = "helm",
+    feature = "maven",
+    feature = "nuget",
+    feature = "packagist"
+))]
+pub(crate) fn latest_stable<I, S>(versions: I) -> Option<Version>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    versions
+        .into_iter()
+        .filter_map(|version| parse_version(version.as_ref()))
+        .filter(|version| version.pre.is_empty())
+        .max()
+}
+
+/// Parse a version that might not be valid semver, such as `2.0`, `v1.2.3`, `2.0rc1`, or
+/// `33.0.0-jre`.
+///
+/// Missing components are filled in with zeros. Suffixes that look like pre-release markers
+/// (`alpha`, `beta`, `rc`, etc.) become pre-releases and everything else becomes build metadata,
+/// which is ignored when matching version requirements.
+pub(crate) fn parse_version(version: &str) -> Option<Version> {
+    let version = version.trim().trim_start_matches(&['v', 'V'][..]);
+
+    if let Ok(version) = version.parse::<Version>() {
+        if version.pre.is_empty() || is_prerelease_marker(version.pre.as_str()) {
+            return Some(version);
+        }
+    }
+
+    let numeric_len = version
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(version.len());
+    let (numeric, suffix) = version.split_at(numeric_len);
+
+    let mut components = numeric
+        .split('.')
+        .filter(|component| !component.is_empty())
+        .map(|component| component.parse::<u64>().ok());
+    let major = components.next()??;
+    let minor = components.next().unwrap_or(Some(0))?;
+    let patch = components.next().unwrap_or(Some(0))?;
+    let extra = components
+        .map(|component| component.map(|c| c.to_string()))
+        .collect::<Option<Vec<_>>>()?;
+
+    let mut out = Version::new(major, minor, patch);
+
+    let suffix = sanitize_identifiers(suffix);
+    let mut build = extra;
+    if !suffix.is_empty() {
+        if is_prerelease_marker(&suffix) {
+            out.pre = Prerelease::new(&suffix).ok()?;
+        } else {
+            build.push(suffix);
+        }
+    }
+
+    if !build.is_empty() {
+        out.build = BuildMetadata::new(&build.join(".")).ok()?;
+    }
+
+    Some(out)
+}
+
+/// Whether `suffix` starts with something like `alpha`, `beta`, or `rc`.
+fn is_prerelease_marker(suffix: &str) -> bool {
+    const PRERELEASE_MARKERS: &[&str] = &[
+        "a", "alpha", "b", "beta", "c", "dev", "m", "pre", "preview", "rc", "snapshot",
+    ];
+
+    let word = suffix
+        .split(|c: char| !c.is_ascii_alphabetic())
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+
+    PRERELEASE_MARKERS.contains(&&*word)
+}
+
+/// Turn arbitrary text into dot separated semver identifiers.
+fn sanitize_identifiers(text: &str) -> String {
+    text.split(|c: char| !c.is_ascii_alphanumeric() && c != '-')
+        .filter(|identifier| !identifier.is_empty())
+        .map(|identifier| {
+            let identifier = identifier.trim_matches('-');
+            if identifier.chars().all(|c| c.is_ascii_digit()) {
+                let trimmed = identifier.trim_start_matches('0');
+                if trimmed.is_empty() { "0" } else { trimmed }.to_owned()
+            } else {
+                identifier.to_owned()
+            }
+        })
+        .filter(|identifier| !identifier.is_empty())
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// The registry doesn't have the package, for registries that respond with an empty result
+/// rather than `404 Not Found`.
+///
+/// Like [`crate::http::NotFound`] this most likely means the macro was given the wrong input, so
+/// it's reported as a compile error.
+#[allow(dead_code)]
+#[derive(Debug)]
+pub(crate) struct NotInRegistry {
+    pub(crate) name: String,
+    pub(crate) registry: String,
+}
+
+impl fmt::Display for NotInRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}` wasn't found in {}. Double check the arguments to the macro.",
+            self.name, self.registry
+        )
+    }
+}
+
+impl std::error::Error for NotInRegistry {}
+
+pub(crate) struct Input {
+    pub(crate) name: String,
+    pub(crate) version_req: VersionReq,
+}
+
+impl Parse for Input {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let name = input.parse::<syn::LitStr>()?.value();
+
+        input.parse::<syn::token::Comma>()?;
+
+        let lit = input.parse::<syn::LitStr>()?;
+        let version_req = lit
+            .value()
+            .parse()
+            .map_err(|err| syn::Error::new(lit.span(), err))?;
+
+        input.parse::<syn::token::Comma>().ok();
+
+        Ok(Self { name, version_req })
+    }
+}
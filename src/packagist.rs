@@ -0,0 +1,44 @@
+use crate::{http::request, package};
+use anyhow::{Context as _, Result};
+use hyper::Request;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+pub(crate) fn packagist(input: package::Input) -> Result<Option<String>> {
+    #[derive(Debug, Deserialize)]
+    struct Response {
+        packages: HashMap<String, Vec<PackageVersion>>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct PackageVersion {
+        version: String,
+    }
+
+    let data = request::<Response>(
+        Request::builder()
+            .uri(format!("https://repo.packagist.org/p2/{}.json", input.name))
+            .body(())
+            .unwrap(),
+    )?;
+
+    let versions = data
+        .packages
+        .get(&input.name)
+        .context("Package missing from response")?;
+
+    let latest_version = package::latest_stable(versions.iter().map(|v| &v.version))
+        .context("No stable versions found for package")?;
+
+    Ok(package::check(&input, "Packagist", &latest_version))
+}
+
+/// ```compile_fail
+/// todo_or_die::packagist!("laravel/framework", ">=10");
+/// ```
+///
+/// ```
+/// todo_or_die::packagist!("laravel/framework", ">=1000");
+/// ```
+#[allow(dead_code)]
+fn tests() {}
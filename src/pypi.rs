@@ -0,0 +1,38 @@
+use crate::{http::request, package};
+use anyhow::{Context as _, Result};
+use hyper::Request;
+use serde::Deserialize;
+
+pub(crate) fn pypi(input: package::Input) -> Result<Option<String>> {
+    #[derive(Debug, Deserialize)]
+    struct Response {
+        info: Info,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Info {
+        version: String,
+    }
+
+    let data = request::<Response>(
+        Request::builder()
+            .uri(format!("https://pypi.org/pypi/{}/json", input.name))
+            .body(())
+            .unwrap(),
+    )?;
+
+    let latest_version =
+        package::parse_version(&data.info.version).context("Couldn't parse latest version")?;
+
+    Ok(package::check(&input, "PyPI", &latest_version))
+}
+
+/// ```compile_fail
+/// todo_or_die::pypi!("numpy", ">=1.0");
+/// ```
+///
+/// ```
+/// todo_or_die::pypi!("numpy", ">=1000");
+/// ```
+#[allow(dead_code)]
+fn tests() {}
@@ -0,0 +1,36 @@
+use crate::{http::request, package};
+use anyhow::{Context as _, Result};
+use hyper::Request;
+use serde::Deserialize;
+
+pub(crate) fn rubygems(input: package::Input) -> Result<Option<String>> {
+    #[derive(Debug, Deserialize)]
+    struct Response {
+        version: String,
+    }
+
+    let data = request::<Response>(
+        Request::builder()
+            .uri(format!(
+                "https://rubygems.org/api/v1/versions/{}/latest.json",
+                input.name
+            ))
+            .body(())
+            .unwrap(),
+    )?;
+
+    let latest_version =
+        package::parse_version(&data.version).context("Couldn't parse latest version")?;
+
+    Ok(package::check(&input, "RubyGems", &latest_version))
+}
+
+/// ```compile_fail
+/// todo_or_die::rubygems!("rails", ">=7.0");
+/// ```
+///
+/// ```
+/// todo_or_die::rubygems!("rails", ">=1000");
+/// ```
+#[allow(dead_code)]
+fn tests() {}
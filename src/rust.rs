@@ -1,15 +1,23 @@
 use anyhow::{Context as _, Result};
 use semver::VersionReq;
+use std::fmt;
 use syn::parse::Parse;
 use version_check::Version;
 
 pub(crate) fn rust_version(input: Input) -> Result<Option<String>> {
-    let current_version = Version::read()
+    let mut current_version = Version::read()
         .context("Unable to get current rust version")?
         .to_string()
         .parse::<semver::Version>()
         .context("Couldn't parse rust version")?;
 
+    if input.include_prereleases {
+        if let Some(pre) = prerelease() {
+            current_version.pre = semver::Prerelease::new(&pre)
+                .with_context(|| format!("Invalid pre-release `{}`", pre))?;
+        }
+    }
+
     if input.version_req.matches(&current_version) {
         Ok(Some(format!(
             "Your active version of rust is {}. Time to act on this!",
@@ -20,11 +28,205 @@ pub(crate) fn rust_version(input: Input) -> Result<Option<String>> {
     }
 }
 
+/// The channel and commit date of the active toolchain as a semver pre-release, such as
+/// `nightly.20241201`, or `None` if it's a stable toolchain.
+fn prerelease() -> Option<String> {
+    let channel = version_check::Channel::read()?;
+
+    let channel = if channel.is_nightly() {
+        "nightly"
+    } else if channel.is_beta() {
+        "beta"
+    } else if channel.is_dev() {
+        "dev"
+    } else {
+        return None;
+    };
+
+    match version_check::Date::read() {
+        Some(date) => Some(format!("{}.{}", channel, date.to_string().replace('-', ""))),
+        None => Some(channel.to_owned()),
+    }
+}
+
+pub(crate) fn toolchain_age(input: ToolchainAge) -> Result<Option<String>> {
+    let date = version_check::Date::read().context("Unable to get commit date of rustc")?;
+    let date = chrono::NaiveDate::parse_from_str(&date.to_string(), "%Y-%m-%d")
+        .context("Couldn't parse commit date of rustc")?;
+
+    let deadline = date
+        .checked_add_months(chrono::Months::new(input.months))
+        .context("Date out of range")?;
+
+    if deadline <= crate::clock::now()?.date_naive() {
+        Ok(Some(format!(
+            "Your active rust toolchain was built from a commit on {}, more than {} months ago. Time to act on this!",
+            date, input.months
+        )))
+    } else {
+        Ok(None)
+    }
+}
+
+pub(crate) fn clippy_version(input: ToolVersion) -> Result<Option<String>> {
+    let driver = match clippy_driver() {
+        Some(driver) => driver,
+        None => return Ok(None),
+    };
+
+    let output = std::process::Command::new(&driver)
+        .arg("--version")
+        .output()
+        .with_context(|| format!("Failed to run `{} --version`", driver))?;
+
+    // the output looks like `clippy 0.1.85 (4d91de4e48 2025-02-17)`
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let current_version = stdout
+        .split_whitespace()
+        .nth(1)
+        .and_then(|version| version.parse::<semver::Version>().ok())
+        .with_context(|| format!("Couldn't parse clippy version from `{}`", stdout.trim()))?;
+
+    if input.version_req.matches(&current_version) {
+        Ok(Some(format!(
+            "Your active version of clippy is {}. Time to act on this!",
+            current_version
+        )))
+    } else {
+        Ok(None)
+    }
+}
+
+pub(crate) fn llvm_version(input: ToolVersion) -> Result<Option<String>> {
+    // use the same rustc as `version_check` does
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_owned());
+
+    let output = std::process::Command::new(&rustc)
+        .arg("-vV")
+        .output()
+        .with_context(|| format!("Failed to run `{} -vV`", rustc))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let llvm_version = stdout
+        .lines()
+        .find_map(|line| line.strip_prefix("LLVM version:"))
+        .map(str::trim)
+        .with_context(|| format!("`{} -vV` didn't report an LLVM version", rustc))?;
+    let current_version = parse_rust_version(llvm_version)
+        .with_context(|| format!("Couldn't parse LLVM version `{}`", llvm_version))?;
+
+    if input.version_req.matches(&current_version) {
+        Ok(Some(format!(
+            "The version of LLVM bundled with your active rust toolchain is {}. Time to act on this!",
+            current_version
+        )))
+    } else {
+        Ok(None)
+    }
+}
+
+/// The path to `clippy-driver`, if we're being compiled by clippy.
+///
+/// `cargo clippy` works by setting `clippy-driver` as the rustc wrapper.
+fn clippy_driver() -> Option<String> {
+    ["RUSTC_WORKSPACE_WRAPPER", "RUSTC_WRAPPER"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|wrapper| std::path::Path::new(wrapper).file_stem() == Some("clippy-driver".as_ref()))
+}
+
+pub(crate) fn rust_channel(input: ChannelInput) -> Result<Option<String>> {
+    let channel = version_check::Channel::read().context("Unable to get current rust channel")?;
+
+    let matches = match input.channel {
+        Channel::Stable => channel.is_stable(),
+        Channel::Beta => channel.is_beta(),
+        Channel::Nightly => channel.is_nightly(),
+        Channel::Dev => channel.is_dev(),
+    };
+
+    if matches {
+        Ok(Some(format!(
+            "You're building with a {} toolchain. Time to act on this!",
+            input.channel
+        )))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Parse a rust version such as `1.80` or `1.80.1`.
+pub(crate) fn parse_rust_version(version: &str) -> Option<semver::Version> {
+    let mut components = version.trim().split('.');
+    let major = components.next()?.parse().ok()?;
+    let minor = components.next()?.parse().ok()?;
+    let patch = components.next().unwrap_or("0").parse().ok()?;
+    if components.next().is_some() {
+        return None;
+    }
+    Some(semver::Version::new(major, minor, patch))
+}
+
 pub(crate) struct Input {
     version_req: VersionReq,
+    include_prereleases: bool,
 }
 
 impl Parse for Input {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let lit = input.parse::<syn::LitStr>()?;
+        let version_req = lit
+            .value()
+            .parse()
+            .map_err(|err| syn::Error::new(lit.span(), err))?;
+
+        input.parse::<syn::token::Comma>().ok();
+
+        let mut include_prereleases = false;
+        if !input.is_empty() {
+            let ident = input.parse::<syn::Ident>()?;
+            if ident != "include_prereleases" {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    "expected `include_prereleases`",
+                ));
+            }
+            include_prereleases = true;
+
+            input.parse::<syn::token::Comma>().ok();
+        }
+
+        Ok(Self {
+            version_req,
+            include_prereleases,
+        })
+    }
+}
+
+pub(crate) struct ToolchainAge {
+    months: u32,
+}
+
+impl Parse for ToolchainAge {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let ident = input.parse::<syn::Ident>()?;
+        if ident != "months" {
+            return Err(syn::Error::new(ident.span(), "expected `months`"));
+        }
+        input.parse::<syn::token::Eq>()?;
+        let months = input.parse::<syn::LitInt>()?.base10_parse()?;
+
+        input.parse::<syn::token::Comma>().ok();
+
+        Ok(Self { months })
+    }
+}
+
+pub(crate) struct ToolVersion {
+    version_req: VersionReq,
+}
+
+impl Parse for ToolVersion {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
         let lit = input.parse::<syn::LitStr>()?;
         let version_req = lit
@@ -38,6 +240,53 @@ impl Parse for Input {
     }
 }
 
+pub(crate) struct ChannelInput {
+    channel: Channel,
+}
+
+#[derive(Clone, Copy)]
+enum Channel {
+    Stable,
+    Beta,
+    Nightly,
+    Dev,
+}
+
+impl fmt::Display for Channel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Channel::Stable => write!(f, "stable"),
+            Channel::Beta => write!(f, "beta"),
+            Channel::Nightly => write!(f, "nightly"),
+            Channel::Dev => write!(f, "dev"),
+        }
+    }
+}
+
+impl Parse for ChannelInput {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let lit = input.parse::<syn::LitStr>()?;
+        let channel = match &*lit.value() {
+            "stable" => Channel::Stable,
+            "beta" => Channel::Beta,
+            "nightly" => Channel::Nightly,
+            "dev" => Channel::Dev,
+            _ => {
+                return Err(syn::Error::new(
+                    lit.span(),
+                    "expected one of `stable`, `beta`, `nightly`, or `dev`",
+                ))
+            }
+        };
+
+        input.parse::<syn::token::Comma>().ok();
+
+        Ok(Self { channel })
+    }
+}
+
+/// # `rust_version`
+///
 /// ```compile_fail
 /// todo_or_die::rust_version!(">1.50");
 /// ```
@@ -45,5 +294,52 @@ impl Parse for Input {
 /// ```
 /// todo_or_die::rust_version!("=2.0.0");
 /// ```
+///
+/// ```
+/// todo_or_die::rust_version!("=2.0.0", include_prereleases);
+/// ```
+///
+/// unknown flag
+/// ```compile_fail
+/// todo_or_die::rust_version!("=2.0.0", include_nightly);
+/// ```
+///
+/// # `toolchain_age`
+///
+/// ```compile_fail
+/// todo_or_die::toolchain_age!(months = 0);
+/// ```
+///
+/// ```
+/// todo_or_die::toolchain_age!(months = 100000);
+/// ```
+///
+/// # `clippy_version`
+///
+/// does nothing when not running clippy
+/// ```
+/// todo_or_die::clippy_version!(">=0.1.0");
+/// ```
+///
+/// # `llvm_version`
+///
+/// ```compile_fail
+/// todo_or_die::llvm_version!(">=1");
+/// ```
+///
+/// ```
+/// todo_or_die::llvm_version!("<1");
+/// ```
+///
+/// # `rust_channel`
+///
+/// ```
+/// todo_or_die::rust_channel!("dev");
+/// ```
+///
+/// unknown channel
+/// ```compile_fail
+/// todo_or_die::rust_channel!("canary");
+/// ```
 #[allow(dead_code)]
 fn tests() {}
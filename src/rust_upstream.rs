@@ -0,0 +1,537 @@
+use crate::{
+    http::{request_text, NotFoundIsntFatal},
+    rust::parse_rust_version,
+};
+use anyhow::{Context as _, Result};
+use hyper::Request;
+use syn::parse::Parse;
+
+pub(crate) fn after_rust_release(input: RustRelease) -> Result<Option<String>> {
+    let stable = stable_rust_version()?;
+
+    if stable >= input.version {
+        Ok(Some(format!(
+            "Rust {} has been released. The latest stable version is {}. Time to act on this!",
+            input.version, stable
+        )))
+    } else {
+        Ok(None)
+    }
+}
+
+pub(crate) fn msrv_drift(input: MsrvDrift) -> Result<Option<String>> {
+    // cargo sets this from `package.rust-version`, including when it's inherited from the
+    // workspace
+    let msrv = std::env::var("CARGO_PKG_RUST_VERSION").unwrap_or_default();
+    if msrv.is_empty() {
+        anyhow::bail!("`rust-version` isn't set in Cargo.toml");
+    }
+    let msrv = parse_rust_version(&msrv)
+        .with_context(|| format!("Couldn't parse rust-version `{}`", msrv))?;
+
+    let stable = stable_rust_version()?;
+    let behind = releases_between(&msrv, &stable);
+
+    if behind > input.releases {
+        Ok(Some(format!(
+            "rust-version is {}, which is {} releases behind the latest stable version {}. Time to act on this!",
+            msrv, behind, stable
+        )))
+    } else {
+        Ok(None)
+    }
+}
+
+pub(crate) fn rust_releases_since(input: ReleasesSince) -> Result<Option<String>> {
+    let stable = stable_rust_version()?;
+    let since = releases_between(&input.version, &stable);
+
+    if since >= input.releases {
+        Ok(Some(format!(
+            "{} stable releases of rust have shipped since {}. The latest is {}. Time to act on this!",
+            since, input.version, stable
+        )))
+    } else {
+        Ok(None)
+    }
+}
+
+/// The number of minor releases from `from` to `to`, or zero if `to` isn't newer.
+fn releases_between(from: &semver::Version, to: &semver::Version) -> u64 {
+    if from.major != to.major {
+        return 0;
+    }
+    to.minor.saturating_sub(from.minor)
+}
+
+/// The version of the latest stable release of rust, regardless of which toolchain is active.
+fn stable_rust_version() -> Result<semver::Version> {
+    // the `stable` branch always points at the latest stable release
+    let version = rust_lang_source("rust", "stable", "src/version")?;
+    parse_rust_version(&version)
+        .with_context(|| format!("Couldn't parse rust version `{}`", version.trim()))
+}
+
+pub(crate) fn feature_stabilized(input: FeatureName) -> Result<Option<String>> {
+    let accepted = feature_table("accepted")?;
+
+    if let Some(entry) = find_feature(&accepted, "accepted", &input.name) {
+        let version = string_literals(entry)
+            .next()
+            .unwrap_or("an unknown version");
+        Ok(Some(format!(
+            "The `{}` feature was stabilized in rust {}. Time to act on this!",
+            input.name, version
+        )))
+    } else {
+        Ok(None)
+    }
+}
+
+pub(crate) fn feature_removed(input: FeatureName) -> Result<Option<String>> {
+    let removed = feature_table("removed")?;
+
+    if let Some(entry) = find_feature(&removed, "removed", &input.name) {
+        let mut literals = string_literals(entry);
+        let version = literals.next().unwrap_or("an unknown version");
+        let msg = match literals.next() {
+            Some(reason) => format!(
+                "The `{}` feature was removed in rust {} ({}). Time to act on this!",
+                input.name, version, reason
+            ),
+            None => format!(
+                "The `{}` feature was removed in rust {}. Time to act on this!",
+                input.name, version
+            ),
+        };
+        Ok(Some(msg))
+    } else {
+        // library features, such as `iter_array_chunks`, aren't in the compiler's feature gate
+        // tables at all, so only features listed in `removed.rs` are reported
+        Ok(None)
+    }
+}
+
+pub(crate) fn edition_available(input: Edition) -> Result<Option<String>> {
+    let source = rust_lang_source("rust", "stable", "compiler/rustc_span/src/edition.rs")?;
+
+    let is_stable = source
+        .split("fn is_stable")
+        .nth(1)
+        .context("Couldn't find `is_stable` in edition.rs")?;
+    let is_stable = is_stable.split("fn ").next().unwrap_or(is_stable);
+
+    let needle = format!("Edition{} => true", input.year);
+    if is_stable.contains(&needle) {
+        Ok(Some(format!(
+            "The {} edition is available on stable rust. Time to act on this!",
+            input.year
+        )))
+    } else {
+        Ok(None)
+    }
+}
+
+pub(crate) fn cargo_unstable_stabilized(input: CargoFlag) -> Result<Option<String>> {
+    // cargo keeps stabilized `-Z` flags around so it can tell people they're no longer needed,
+    // with entries like `"sparse-registry" => stabilized_warn(k, "1.68", STABILISED_SPARSE_REGISTRY),`
+    let features = rust_lang_source("cargo", "master", "src/cargo/core/features.rs")?;
+
+    let needle = format!("\"{}\" => stabilized_", input.flag);
+    let entry = match features.find(&needle) {
+        Some(start) => &features[start + needle.len()..],
+        None => return Ok(None),
+    };
+    let entry = entry.split('\n').next().unwrap_or(entry);
+
+    let version = string_literals(entry)
+        .next()
+        .unwrap_or("an unknown version");
+
+    Ok(Some(format!(
+        "`-Z {}` was stabilized in cargo {}. Time to act on this!",
+        input.flag, version
+    )))
+}
+
+pub(crate) fn target_tier(input: TargetTier) -> Result<Option<String>> {
+    let platform_support =
+        rust_lang_source("rust", "master", "src/doc/rustc/src/platform-support.md")?;
+
+    let tier = match find_target_tier(&platform_support, &input.target) {
+        Some(tier) => tier,
+        None => return Ok(None),
+    };
+
+    if tier <= input.tier {
+        Ok(Some(format!(
+            "`{}` is a tier {} target. Time to act on this!",
+            input.target, tier
+        )))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Find the tier of a target in the platform support docs.
+///
+/// Targets are listed in tables under headings like `## Tier 2 with Host Tools`, with the target
+/// name in the first column, such as ``[`wasm32-wasip2`](platform-support/wasm32-wasip2.md) | ✓ |``.
+fn find_target_tier(platform_support: &str, target: &str) -> Option<u8> {
+    let needle = format!("`{}`", target);
+    let mut tier = None;
+
+    for line in platform_support.lines() {
+        if let Some(heading) = line.strip_prefix("## Tier ") {
+            tier = heading
+                .chars()
+                .next()
+                .and_then(|c| c.to_digit(10))
+                .map(|n| n as u8);
+            continue;
+        }
+
+        let first_column = line.split('|').next().unwrap_or_default().trim();
+        if first_column.trim_start_matches('[').starts_with(&needle) {
+            return tier;
+        }
+    }
+
+    None
+}
+
+/// Fetch one of the feature gate tables from the compiler's source, such as `accepted.rs`.
+fn feature_table(name: &str) -> Result<String> {
+    rust_lang_source(
+        "rust",
+        "master",
+        &format!("compiler/rustc_feature/src/{}.rs", name),
+    )
+}
+
+/// Fetch a file from a branch of a repository in the rust-lang organization, such as
+/// rust-lang/rust.
+fn rust_lang_source(repo: &str, branch: &str, path: &str) -> Result<String> {
+    request_text(
+        Request::builder()
+            .uri(format!(
+                "https://raw.githubusercontent.com/rust-lang/{}/{}/{}",
+                repo, branch, path
+            ))
+            .extension(NotFoundIsntFatal)
+            .body(())
+            .unwrap(),
+    )
+}
+
+/// Find the entry for a feature in a feature gate table.
+///
+/// Entries look like `(accepted, generic_associated_types, "1.65.0", Some(44265)),` and might
+/// span several lines.
+fn find_feature<'a>(table: &'a str, status: &str, name: &str) -> Option<&'a str> {
+    let needle = format!("({}, {},", status, name);
+    let start = table.find(&needle)?;
+    let rest = &table[start + needle.len()..];
+    let end = rest.find("),\n").map_or(rest.len(), |end| end + 1);
+    Some(&rest[..end])
+}
+
+/// The contents of the string literals in a feature gate table entry, such as the version and the
+/// reason a feature was removed.
+fn string_literals(entry: &str) -> impl Iterator<Item = &str> {
+    entry.split('"').skip(1).step_by(2)
+}
+
+pub(crate) struct RustRelease {
+    version: semver::Version,
+}
+
+impl Parse for RustRelease {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let lit = input.parse::<syn::LitStr>()?;
+        let version = parse_rust_version(&lit.value()).ok_or_else(|| {
+            syn::Error::new(
+                lit.span(),
+                "expected a rust version, such as `1.80` or `1.80.1`",
+            )
+        })?;
+
+        input.parse::<syn::token::Comma>().ok();
+
+        Ok(Self { version })
+    }
+}
+
+pub(crate) struct MsrvDrift {
+    releases: u64,
+}
+
+impl Parse for MsrvDrift {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let ident = input.parse::<syn::Ident>()?;
+        if ident != "releases" {
+            return Err(syn::Error::new(ident.span(), "expected `releases`"));
+        }
+        input.parse::<syn::token::Eq>()?;
+        let releases = input.parse::<syn::LitInt>()?.base10_parse()?;
+
+        input.parse::<syn::token::Comma>().ok();
+
+        Ok(Self { releases })
+    }
+}
+
+pub(crate) struct ReleasesSince {
+    version: semver::Version,
+    releases: u64,
+}
+
+impl Parse for ReleasesSince {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let RustRelease { version } = input.parse::<RustRelease>()?;
+
+        let releases = input.parse::<syn::LitInt>()?.base10_parse()?;
+
+        input.parse::<syn::token::Comma>().ok();
+
+        Ok(Self { version, releases })
+    }
+}
+
+pub(crate) struct CargoFlag {
+    flag: String,
+}
+
+impl Parse for CargoFlag {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let lit = input.parse::<syn::LitStr>()?;
+        let value = lit.value();
+        let flag = value.trim().trim_start_matches("-Z").trim();
+        if flag.is_empty() || !flag.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+            return Err(syn::Error::new(
+                lit.span(),
+                "expected the name of a cargo unstable flag, such as `minimal-versions`",
+            ));
+        }
+
+        input.parse::<syn::token::Comma>().ok();
+
+        Ok(Self {
+            flag: flag.to_owned(),
+        })
+    }
+}
+
+pub(crate) struct FeatureName {
+    name: String,
+}
+
+impl Parse for FeatureName {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let lit = input.parse::<syn::LitStr>()?;
+        let name = lit.value();
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(syn::Error::new(
+                lit.span(),
+                "expected the name of a feature gate, such as `generic_associated_types`",
+            ));
+        }
+
+        input.parse::<syn::token::Comma>().ok();
+
+        Ok(Self { name })
+    }
+}
+
+pub(crate) struct TargetTier {
+    target: String,
+    tier: u8,
+}
+
+impl Parse for TargetTier {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let target = input.parse::<syn::LitStr>()?.value();
+        input.parse::<syn::token::Comma>()?;
+
+        let lit = input.parse::<syn::LitStr>()?;
+        let tier = match &*lit.value() {
+            "tier1" => 1,
+            "tier2" => 2,
+            "tier3" => 3,
+            _ => {
+                return Err(syn::Error::new(
+                    lit.span(),
+                    "expected one of `tier1`, `tier2`, or `tier3`",
+                ))
+            }
+        };
+
+        input.parse::<syn::token::Comma>().ok();
+
+        Ok(Self { target, tier })
+    }
+}
+
+pub(crate) struct Edition {
+    year: u32,
+}
+
+impl Parse for Edition {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let lit = input.parse::<syn::LitStr>()?;
+        let year = lit
+            .value()
+            .parse()
+            .ok()
+            .filter(|year| (2015..10000).contains(year))
+            .ok_or_else(|| syn::Error::new(lit.span(), "expected an edition, such as `2027`"))?;
+
+        input.parse::<syn::token::Comma>().ok();
+
+        Ok(Self { year })
+    }
+}
+
+/// # `after_rust_release`
+///
+/// released version
+/// ```compile_fail
+/// todo_or_die::after_rust_release!("1.80");
+/// ```
+///
+/// version that hasn't been released yet
+/// ```
+/// todo_or_die::after_rust_release!("1.1000.0");
+/// ```
+///
+/// not a rust version
+/// ```compile_fail
+/// todo_or_die::after_rust_release!(">=1.80");
+/// ```
+///
+/// # `rust_releases_since`
+///
+/// enough releases since
+/// ```compile_fail
+/// todo_or_die::rust_releases_since!("1.74", 6);
+/// ```
+///
+/// not enough releases since
+/// ```
+/// todo_or_die::rust_releases_since!("1.74", 1000);
+/// ```
+///
+/// missing number of releases
+/// ```compile_fail
+/// todo_or_die::rust_releases_since!("1.74");
+/// ```
+///
+/// # `msrv_drift`
+///
+/// this crate doesn't set `rust-version`, which is reported as a warning
+/// ```
+/// todo_or_die::msrv_drift!(releases = 4);
+/// ```
+///
+/// missing `releases`
+/// ```compile_fail
+/// todo_or_die::msrv_drift!(4);
+/// ```
+///
+/// # `feature_stabilized`
+///
+/// stabilized feature
+/// ```compile_fail
+/// todo_or_die::feature_stabilized!("generic_associated_types");
+/// ```
+///
+/// unstable feature
+/// ```
+/// todo_or_die::feature_stabilized!("specialization");
+/// ```
+///
+/// not a feature name
+/// ```compile_fail
+/// todo_or_die::feature_stabilized!("#![feature(specialization)]");
+/// ```
+///
+/// # `feature_removed`
+///
+/// removed feature
+/// ```compile_fail
+/// todo_or_die::feature_removed!("box_syntax");
+/// ```
+///
+/// library features aren't in the compiler's feature gate tables
+/// ```
+/// todo_or_die::feature_removed!("iter_array_chunks");
+/// todo_or_die::feature_removed!("test");
+/// ```
+///
+/// unstable feature
+/// ```
+/// todo_or_die::feature_removed!("specialization");
+/// ```
+///
+/// stabilized feature
+/// ```
+/// todo_or_die::feature_removed!("generic_associated_types");
+/// ```
+///
+/// # `cargo_unstable_stabilized`
+///
+/// stabilized flag
+/// ```compile_fail
+/// todo_or_die::cargo_unstable_stabilized!("sparse-registry");
+/// ```
+///
+/// with `-Z` prefix
+/// ```compile_fail
+/// todo_or_die::cargo_unstable_stabilized!("-Zsparse-registry");
+/// ```
+///
+/// unstable flag
+/// ```
+/// todo_or_die::cargo_unstable_stabilized!("minimal-versions");
+/// ```
+///
+/// # `target_tier`
+///
+/// tier 1 target
+/// ```compile_fail
+/// todo_or_die::target_tier!("x86_64-unknown-linux-gnu", "tier1");
+/// ```
+///
+/// reaching a lower tier also counts
+/// ```compile_fail
+/// todo_or_die::target_tier!("x86_64-unknown-linux-gnu", "tier3");
+/// ```
+///
+/// unknown target
+/// ```
+/// todo_or_die::target_tier!("not-a-real-target", "tier3");
+/// ```
+///
+/// unknown tier
+/// ```compile_fail
+/// todo_or_die::target_tier!("x86_64-unknown-linux-gnu", "tier4");
+/// ```
+///
+/// # `edition_available`
+///
+/// stable edition
+/// ```compile_fail
+/// todo_or_die::edition_available!("2021");
+/// ```
+///
+/// edition that doesn't exist yet
+/// ```
+/// todo_or_die::edition_available!("2099");
+/// ```
+///
+/// not an edition
+/// ```compile_fail
+/// todo_or_die::edition_available!("next");
+/// ```
+#[allow(dead_code)]
+fn tests() {}
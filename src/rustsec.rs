@@ -0,0 +1,276 @@
+use crate::http::{request_optional, request_text, NotFoundIsntFatal};
+use anyhow::{Context as _, Result};
+use chrono::NaiveDate;
+use hyper::Request;
+use semver::{Version, VersionReq};
+use serde::Deserialize;
+use syn::parse::Parse;
+
+pub(crate) fn rustsec(input: Input) -> Result<Option<String>> {
+    let Input {
+        krate,
+        since,
+        version,
+    } = input;
+
+    let advisories = advisories(&krate, since)?;
+
+    let ids = advisories
+        .iter()
+        .filter(|advisory| advisory.advisory.informational.is_none())
+        .filter(|advisory| {
+            version
+                .as_ref()
+                .is_none_or(|version| advisory.affects(version))
+        })
+        .map(|advisory| advisory.advisory.id.as_str())
+        .collect::<Vec<_>>();
+
+    if let [id] = &*ids {
+        Ok(Some(format!(
+            "Security advisory {} has been published for {}. Time to act on this!",
+            id, krate
+        )))
+    } else if !ids.is_empty() {
+        Ok(Some(format!(
+            "Security advisories {} have been published for {}. Time to act on this!",
+            ids.join(", "),
+            krate
+        )))
+    } else {
+        Ok(None)
+    }
+}
+
+pub(crate) fn crate_unmaintained(input: CrateName) -> Result<Option<String>> {
+    let advisories = advisories(&input.krate, None)?;
+
+    let advisory = advisories
+        .iter()
+        .find(|advisory| advisory.advisory.informational.as_deref() == Some("unmaintained"));
+
+    if let Some(advisory) = advisory {
+        Ok(Some(format!(
+            "{} has been marked as unmaintained in {}. Time to act on this!",
+            input.krate, advisory.advisory.id
+        )))
+    } else {
+        Ok(None)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Advisory {
+    advisory: Metadata,
+    #[serde(default)]
+    versions: Versions,
+}
+
+#[derive(Debug, Deserialize)]
+struct Metadata {
+    id: String,
+    date: String,
+    informational: Option<String>,
+    withdrawn: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Versions {
+    #[serde(default)]
+    patched: Vec<String>,
+    #[serde(default)]
+    unaffected: Vec<String>,
+}
+
+impl Advisory {
+    /// Whether `version` is affected, meaning it isn't patched or unaffected.
+    fn affects(&self, version: &Version) -> bool {
+        !self
+            .versions
+            .patched
+            .iter()
+            .chain(&self.versions.unaffected)
+            .filter_map(|req| req.parse::<VersionReq>().ok())
+            .any(|req| req.matches(version))
+    }
+}
+
+/// Fetch the advisories for a crate from the [RustSec advisory database], ignoring withdrawn
+/// advisories and those published before `since`.
+///
+/// [RustSec advisory database]: https://github.com/rustsec/advisory-db
+fn advisories(krate: &str, since: Option<NaiveDate>) -> Result<Vec<Advisory>> {
+    #[derive(Debug, Deserialize)]
+    struct File {
+        name: String,
+        download_url: String,
+    }
+
+    let files = request_optional::<Vec<File>>(
+        Request::builder()
+            .uri(format!(
+                "https://api.github.com/repos/rustsec/advisory-db/contents/crates/{}",
+                krate
+            ))
+            .body(())
+            .unwrap(),
+    )?;
+
+    // crates without any advisories don't have a directory
+    let files = match files {
+        Some(files) => files,
+        None => return Ok(Vec::new()),
+    };
+
+    let mut advisories = Vec::new();
+    for file in files {
+        // ids are `RUSTSEC-YYYY-NNNN` so advisories from before the year of `since` can be
+        // skipped without fetching them
+        let year = file
+            .name
+            .strip_prefix("RUSTSEC-")
+            .and_then(|rest| rest.get(..4))
+            .and_then(|year| year.parse::<i32>().ok());
+        if let (Some(year), Some(since)) = (year, since) {
+            if year < chrono::Datelike::year(&since) {
+                continue;
+            }
+        }
+
+        let text = request_text(
+            Request::builder()
+                .uri(&file.download_url)
+                .extension(NotFoundIsntFatal)
+                .body(())
+                .unwrap(),
+        )?;
+
+        let advisory = parse_advisory(&text)
+            .with_context(|| format!("Failed to parse advisory {}", file.name))?;
+
+        if advisory.advisory.withdrawn.is_some() {
+            continue;
+        }
+
+        let date = NaiveDate::parse_from_str(&advisory.advisory.date, "%Y-%m-%d")
+            .with_context(|| format!("Failed to parse date of advisory {}", file.name))?;
+        if since.is_some_and(|since| date < since) {
+            continue;
+        }
+
+        advisories.push(advisory);
+    }
+
+    Ok(advisories)
+}
+
+/// Advisories are markdown files starting with a fenced block of TOML metadata.
+fn parse_advisory(text: &str) -> Result<Advisory> {
+    let toml = text
+        .trim_start()
+        .strip_prefix("```toml")
+        .and_then(|rest| rest.split("\n```").next())
+        .context("Missing TOML metadata")?;
+
+    Ok(toml::from_str(toml)?)
+}
+
+pub(crate) struct Input {
+    krate: String,
+    since: Option<NaiveDate>,
+    version: Option<Version>,
+}
+
+impl Parse for Input {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let krate = input.parse::<syn::LitStr>()?.value();
+
+        let mut since = None;
+        let mut version = None;
+
+        while input.parse::<syn::token::Comma>().is_ok() && !input.is_empty() {
+            let ident = input.parse::<syn::Ident>()?;
+            input.parse::<syn::token::Eq>()?;
+            let lit = input.parse::<syn::LitStr>()?;
+
+            if ident == "since" {
+                let date = NaiveDate::parse_from_str(&lit.value(), "%Y-%m-%d")
+                    .map_err(|err| syn::Error::new(lit.span(), err))?;
+                since = Some(date);
+            } else if ident == "version" {
+                let value = lit
+                    .value()
+                    .trim()
+                    .parse()
+                    .map_err(|err| syn::Error::new(lit.span(), err))?;
+                version = Some(value);
+            } else {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    "unknown argument. Expected `since` or `version`",
+                ));
+            }
+        }
+
+        Ok(Self {
+            krate,
+            since,
+            version,
+        })
+    }
+}
+
+pub(crate) struct CrateName {
+    krate: String,
+}
+
+impl Parse for CrateName {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let krate = input.parse::<syn::LitStr>()?.value();
+
+        input.parse::<syn::token::Comma>().ok();
+
+        Ok(Self { krate })
+    }
+}
+
+/// # `rustsec`
+///
+/// crate with advisories
+/// ```compile_fail
+/// todo_or_die::rustsec!("openssl");
+/// ```
+///
+/// no advisories after the baseline date
+/// ```
+/// todo_or_die::rustsec!("openssl", since = "3000-01-01");
+/// ```
+///
+/// version that isn't affected
+/// ```
+/// todo_or_die::rustsec!("smallvec", since = "2021-01-01", version = "1.6.1");
+/// ```
+///
+/// crate without advisories
+/// ```
+/// todo_or_die::rustsec!("todo-or-die");
+/// ```
+///
+/// unknown argument
+/// ```compile_fail
+/// todo_or_die::rustsec!("openssl", severity = "high");
+/// ```
+///
+/// # `crate_unmaintained`
+///
+/// unmaintained crate
+/// ```compile_fail
+/// todo_or_die::crate_unmaintained!("term");
+/// ```
+///
+/// maintained crate
+/// ```
+/// todo_or_die::crate_unmaintained!("tokio");
+/// ```
+#[allow(dead_code)]
+fn tests() {}
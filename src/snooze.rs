@@ -0,0 +1,72 @@
+use crate::config::{Config, ConfigError};
+use anyhow::Result;
+use chrono::NaiveDate;
+
+/// Whether the check with the given `id` has been snoozed until a date that hasn't been reached
+/// yet.
+///
+/// Checks are snoozed with `TODO_OR_DIE_SNOOZE`, such as
+/// `retry-shim=2025-12-01,flaky-upstream=2026-01-15`, or in `Cargo.toml` under
+/// `[package.metadata.todo-or-die.snooze]`. The environment variable takes precedence.
+pub(crate) fn is_snoozed(id: &str) -> Result<bool> {
+    let until = match snoozed_until_from_env(id)? {
+        Some(until) => until,
+        None => match Config::read()?.snooze.get(id) {
+            Some(until) => parse_date(until, "`[package.metadata.todo-or-die.snooze]`")?,
+            None => return Ok(false),
+        },
+    };
+
+    Ok(crate::clock::now()?.date_naive() < until)
+}
+
+fn snoozed_until_from_env(id: &str) -> Result<Option<NaiveDate>> {
+    let var = match std::env::var("TODO_OR_DIE_SNOOZE") {
+        Ok(var) => var,
+        Err(_) => return Ok(None),
+    };
+
+    for entry in var
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+    {
+        let (snoozed_id, until) = entry.split_once('=').ok_or_else(|| {
+            ConfigError(format!(
+                "Invalid entry `{}` in `TODO_OR_DIE_SNOOZE`, expected `id=YYYY-MM-DD`",
+                entry
+            ))
+        })?;
+
+        if snoozed_id.trim() == id {
+            return parse_date(until.trim(), "`TODO_OR_DIE_SNOOZE`").map(Some);
+        }
+    }
+
+    Ok(None)
+}
+
+fn parse_date(date: &str, source: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|_| {
+        ConfigError(format!(
+            "Invalid date `{}` in {}, expected a date such as `2025-12-01`",
+            date, source
+        ))
+        .into()
+    })
+}
+
+/// ```
+/// todo_or_die::after_date!(1990, 01, 01, id = "snoozed");
+/// ```
+///
+/// ```compile_fail
+/// todo_or_die::after_date!(1990, 01, 01, id = "snooze-expired");
+/// ```
+///
+/// ```compile_fail
+/// todo_or_die::after_date!(1990, 01, 01, id = "not-snoozed");
+/// ```
+#[cfg(feature = "time")]
+#[allow(dead_code)]
+fn tests() {}
@@ -0,0 +1,165 @@
+use proc_macro2::{TokenStream, TokenTree};
+use quote::quote;
+use syn::parse::Parse;
+
+/// The struct-like form of a check, such as
+/// `check! { when: issue_closed("org/repo#1"), message: "..." }`.
+///
+/// This is only syntax. The check itself isn't parsed here but by the macro named in `when`,
+/// which `check!` expands to, so every macro works and its own modifiers are kept.
+pub(crate) struct CheckSpec {
+    /// The macro performing the check.
+    when: syn::Ident,
+    /// The arguments to the macro.
+    args: TokenStream,
+    message: Option<syn::LitStr>,
+    owner: Option<syn::LitStr>,
+    link: Option<syn::LitStr>,
+}
+
+impl CheckSpec {
+    /// Expand into a call to the macro performing the check, with the other fields passed as
+    /// modifiers.
+    pub(crate) fn expand(self) -> TokenStream {
+        let mut args = self.args.into_iter().collect::<Vec<_>>();
+        if matches!(args.last(), Some(TokenTree::Punct(punct)) if punct.as_char() == ',') {
+            args.pop();
+        }
+
+        let mut args = if args.is_empty() {
+            Vec::new()
+        } else {
+            vec![args.into_iter().collect::<TokenStream>()]
+        };
+
+        let modifiers = [
+            ("message", self.message),
+            ("owner", self.owner),
+            ("link", self.link),
+        ];
+        for (name, value) in modifiers {
+            if let Some(value) = value {
+                let name = syn::Ident::new(name, value.span());
+                args.push(quote! { #name = #value });
+            }
+        }
+
+        let when = self.when;
+        quote! {
+            ::todo_or_die::#when!(#(#args),*);
+        }
+    }
+}
+
+impl Parse for CheckSpec {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut when = None;
+        let mut message = None;
+        let mut owner = None;
+        let mut link = None;
+
+        while !input.is_empty() {
+            let ident = input.parse::<syn::Ident>()?;
+            input.parse::<syn::token::Colon>()?;
+
+            let duplicate = || syn::Error::new(ident.span(), format!("duplicate `{}`", ident));
+
+            if ident == "when" {
+                if when.is_some() {
+                    return Err(duplicate());
+                }
+                let check = input.parse::<syn::Ident>()?;
+                let content;
+                syn::parenthesized!(content in input);
+                when = Some((check, content.parse::<TokenStream>()?));
+            } else {
+                let field = if ident == "message" {
+                    &mut message
+                } else if ident == "owner" {
+                    &mut owner
+                } else if ident == "link" {
+                    &mut link
+                } else {
+                    return Err(syn::Error::new(
+                        ident.span(),
+                        "expected `when`, `message`, `owner`, or `link`",
+                    ));
+                };
+                if field.is_some() {
+                    return Err(duplicate());
+                }
+                *field = Some(input.parse::<syn::LitStr>()?);
+            }
+
+            if input.parse::<syn::token::Comma>().is_err() {
+                break;
+            }
+        }
+
+        let (when, args) = when.ok_or_else(|| input.error("missing `when`"))?;
+
+        Ok(Self {
+            when,
+            args,
+            message,
+            owner,
+            link,
+        })
+    }
+}
+
+/// ```compile_fail
+/// todo_or_die::check! {
+///     when: after_date(1990, 01, 01),
+///     message: "remove the retry shim",
+///     owner: "@alice",
+///     link: "JIRA-42",
+/// }
+/// ```
+///
+/// ```
+/// todo_or_die::check! {
+///     when: after_date(3000, 01, 01, "Europe/Stockholm",),
+///     owner: "@alice",
+/// }
+/// ```
+///
+/// ```
+/// todo_or_die::check!(when: after_date(3000, 01, 01));
+/// ```
+///
+/// inside a function
+/// ```
+/// fn workaround() {
+///     todo_or_die::check! { link: "JIRA-42", when: after_date(3000, 01, 01) }
+/// }
+/// ```
+///
+/// modifiers in the check
+/// ```
+/// todo_or_die::check! {
+///     when: after_date(1990, 01, 01, only(target_os = "no-such-os")),
+///     message: "remove the retry shim",
+/// }
+/// ```
+///
+/// missing `when`
+/// ```compile_fail
+/// todo_or_die::check! { message: "remove the retry shim" }
+/// ```
+///
+/// unknown check
+/// ```compile_fail
+/// todo_or_die::check! { when: no_such_check(1) }
+/// ```
+///
+/// ```compile_fail
+/// todo_or_die::check! { when: after_date(3000, 01, 01), reason: "..." }
+/// ```
+///
+/// ```compile_fail
+/// todo_or_die::check! { when: after_date(3000, 01, 01), message: "a", message: "b" }
+/// ```
+#[cfg(feature = "time")]
+#[allow(dead_code)]
+fn tests() {}
@@ -0,0 +1,90 @@
+use crate::{http::request, package};
+use anyhow::{Context as _, Result};
+use hyper::Request;
+use serde::Deserialize;
+use syn::parse::Parse;
+
+pub(crate) fn terraform_registry(input: Input) -> Result<Option<String>> {
+    #[derive(Debug, Deserialize)]
+    struct Response {
+        version: String,
+    }
+
+    let kind = match input.kind {
+        Kind::Provider => "providers",
+        Kind::Module => "modules",
+    };
+
+    let data = request::<Response>(
+        Request::builder()
+            .uri(format!(
+                "https://registry.terraform.io/v1/{}/{}",
+                kind, input.package.name
+            ))
+            .body(())
+            .unwrap(),
+    )?;
+
+    let latest_version =
+        package::parse_version(&data.version).context("Couldn't parse latest version")?;
+
+    Ok(package::check(
+        &input.package,
+        "the Terraform Registry",
+        &latest_version,
+    ))
+}
+
+pub(crate) struct Input {
+    kind: Kind,
+    package: package::Input,
+}
+
+enum Kind {
+    Provider,
+    Module,
+}
+
+impl Parse for Input {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let lit = input.fork().parse::<syn::LitStr>()?;
+        let package = input.parse::<package::Input>()?;
+
+        let parts = package.name.split('/').collect::<Vec<_>>();
+        if parts.iter().any(|part| part.is_empty()) {
+            return Err(syn::Error::new(
+                lit.span(),
+                "expected `namespace/provider` or `namespace/module/provider`",
+            ));
+        }
+
+        let kind = match parts.len() {
+            2 => Kind::Provider,
+            3 => Kind::Module,
+            _ => {
+                return Err(syn::Error::new(
+                    lit.span(),
+                    "expected `namespace/provider` or `namespace/module/provider`",
+                ))
+            }
+        };
+
+        Ok(Self { kind, package })
+    }
+}
+
+/// provider
+/// ```compile_fail
+/// todo_or_die::terraform_registry!("hashicorp/aws", ">=5.0");
+/// ```
+///
+/// ```
+/// todo_or_die::terraform_registry!("hashicorp/aws", ">=1000");
+/// ```
+///
+/// module
+/// ```compile_fail
+/// todo_or_die::terraform_registry!("terraform-aws-modules/vpc/aws", ">=5.0");
+/// ```
+#[allow(dead_code)]
+fn tests() {}
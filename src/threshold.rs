@@ -0,0 +1,62 @@
+use syn::parse::Parse;
+
+/// A comparison against a number, such as `"> 50 commits"`.
+pub(crate) struct Threshold {
+    op: ThresholdOp,
+    value: u64,
+}
+
+#[derive(Clone, Copy)]
+enum ThresholdOp {
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    Eq,
+}
+
+impl Threshold {
+    pub(crate) fn matches(&self, n: u64) -> bool {
+        match self.op {
+            ThresholdOp::Gt => n > self.value,
+            ThresholdOp::Ge => n >= self.value,
+            ThresholdOp::Lt => n < self.value,
+            ThresholdOp::Le => n <= self.value,
+            ThresholdOp::Eq => n == self.value,
+        }
+    }
+}
+
+impl Parse for Threshold {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let lit = input.parse::<syn::LitStr>()?;
+        let value = lit.value();
+        let value = value.trim();
+
+        let (op, rest) = [
+            (">=", ThresholdOp::Ge),
+            ("<=", ThresholdOp::Le),
+            (">", ThresholdOp::Gt),
+            ("<", ThresholdOp::Lt),
+            ("=", ThresholdOp::Eq),
+        ]
+        .iter()
+        .find_map(|(prefix, op)| value.strip_prefix(prefix).map(|rest| (*op, rest)))
+        .ok_or_else(|| {
+            syn::Error::new(
+                lit.span(),
+                "expected a comparison such as `> 50` (supported operators are >, >=, <, <=, =)",
+            )
+        })?;
+
+        let number = rest
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .replace('_', "")
+            .parse()
+            .map_err(|err| syn::Error::new(lit.span(), err))?;
+
+        Ok(Self { op, value: number })
+    }
+}
@@ -1,49 +1,1388 @@
-use anyhow::Result;
+use crate::{
+    config::{Config, ConfigError},
+    holidays::Holidays,
+    Outcome,
+};
+use anyhow::{Context as _, Result};
 use chrono::prelude::*;
+use chrono_tz::Tz;
+use std::fmt;
 use syn::parse::Parse;
 
-pub(crate) fn after_date(input: Input) -> Result<Option<String>> {
-    let input = NaiveDate::from_ymd(input.year, input.month, input.day);
-    let today = Local::today().naive_local();
+pub(crate) fn after_date(input: AfterDate) -> Result<Outcome> {
+    let input = match input {
+        AfterDate::Date(input) => input,
+        AfterDate::Escalation(input) => return escalate(input),
+    };
 
-    if input <= today {
+    let date = input.options.roll_forward(input.date.to_naive_date())?;
+    let today = today(input.options.timezone)?;
+
+    if date <= today {
+        Ok(Outcome::Die(format!(
+            "{} is now in the past. Time to act on this!",
+            date
+        )))
+    } else {
+        Ok(Outcome::Pass)
+    }
+}
+
+fn escalate(input: Escalation) -> Result<Outcome> {
+    let warn = input.options.roll_forward(input.warn.to_naive_date())?;
+    let die = input.options.roll_forward(input.die.to_naive_date())?;
+    let today = today(input.options.timezone)?;
+
+    if die <= today {
+        Ok(Outcome::Die(format!(
+            "{} is now in the past. Time to act on this!",
+            die
+        )))
+    } else if warn <= today {
+        Ok(Outcome::Warn(format!(
+            "{} is now in the past and this will fail to compile from {}. Time to act on this!",
+            warn, die
+        )))
+    } else {
+        Ok(Outcome::Pass)
+    }
+}
+
+pub(crate) fn before_date(input: Input) -> Result<Option<String>> {
+    let date = input.options.roll_forward(input.date.to_naive_date())?;
+    let today = today(input.options.timezone)?;
+
+    if today < date {
+        Ok(Some(format!(
+            "{} is still in the future. This can't ship yet!",
+            date
+        )))
+    } else {
+        Ok(None)
+    }
+}
+
+pub(crate) fn between_dates(input: DateRange) -> Result<Option<String>> {
+    let start = input.start.to_naive_date();
+    let end = input.end.to_naive_date();
+    let today = today(input.timezone)?;
+
+    if start <= today && today <= end {
+        Ok(Some(format!(
+            "We're between {} and {}. Time to act on this!",
+            start, end
+        )))
+    } else {
+        Ok(None)
+    }
+}
+
+pub(crate) fn after_duration(input: DurationInput) -> Result<Option<String>> {
+    let from = input.from.to_naive_date();
+    let deadline = match input.duration {
+        Duration::Days(days) => from.checked_add_days(chrono::Days::new(days)),
+        Duration::Weeks(weeks) => from.checked_add_days(chrono::Days::new(weeks * 7)),
+        Duration::Months(months) => from.checked_add_months(chrono::Months::new(months)),
+    }
+    .context("Date out of range")?;
+    let deadline = input.options.roll_forward(deadline)?;
+    let today = today(input.options.timezone)?;
+
+    if deadline <= today {
+        Ok(Some(format!(
+            "{} since {} has passed. Time to act on this!",
+            input.duration, from
+        )))
+    } else {
+        Ok(None)
+    }
+}
+
+pub(crate) fn after_quarter(input: Quarter) -> Result<Option<String>> {
+    // months since the start of the fiscal year, counted from January
+    let month0 = input.fiscal_start - 1 + 3 * (input.quarter - 1);
+    let start = NaiveDate::from_ymd_opt(input.year + (month0 / 12) as i32, month0 % 12 + 1, 1)
+        .context("Date out of range")?;
+    let start = input.options.roll_forward(start)?;
+
+    if start <= today(input.options.timezone)? {
+        Ok(Some(format!(
+            "Q{} of {} started on {}. Time to act on this!",
+            input.quarter,
+            if input.fiscal_start == 1 {
+                input.year.to_string()
+            } else {
+                format!("fiscal year {}", input.year)
+            },
+            start
+        )))
+    } else {
+        Ok(None)
+    }
+}
+
+pub(crate) fn after_week(input: Week) -> Result<Option<String>> {
+    let start = input.options.roll_forward(input.start)?;
+
+    if start <= today(input.options.timezone)? {
+        Ok(Some(format!(
+            "Week {} of {} started on {}. Time to act on this!",
+            input.start.iso_week().week(),
+            input.start.iso_week().year(),
+            start
+        )))
+    } else {
+        Ok(None)
+    }
+}
+
+pub(crate) fn after_sprint(input: Sprint) -> Result<Option<String>> {
+    let sprints = Config::read()?.sprints.ok_or_else(|| {
+        ConfigError(
+            "`after_sprint!` requires `[package.metadata.todo-or-die.sprints]` in Cargo.toml"
+                .to_owned(),
+        )
+    })?;
+
+    let first_start = NaiveDate::parse_from_str(&sprints.start, "%Y-%m-%d").map_err(|_| {
+        ConfigError(format!(
+            "Invalid sprint start `{}`, expected a date such as `2025-01-06`",
+            sprints.start
+        ))
+    })?;
+    let sprints_since_first = input.sprint.checked_sub(sprints.first).ok_or_else(|| {
+        ConfigError(format!(
+            "Sprint {} is before the first sprint, {}",
+            input.sprint, sprints.first
+        ))
+    })?;
+
+    let start = first_start
+        .checked_add_days(chrono::Days::new(
+            sprints_since_first.saturating_mul(sprints.length_days),
+        ))
+        .context("Date out of range")?;
+    let start = input.options.roll_forward(start)?;
+
+    if start <= today(input.options.timezone)? {
+        Ok(Some(format!(
+            "Sprint {} started on {}. Time to act on this!",
+            input.sprint, start
+        )))
+    } else {
+        Ok(None)
+    }
+}
+
+pub(crate) fn dies_in(input: DiesIn) -> Result<Option<String>> {
+    let id = input
+        .id
+        .ok_or_else(|| syn::Error::new(proc_macro2::Span::call_site(), "missing `id`"))?;
+
+    let today = today(None)?;
+
+    let first_seen = match crate::marker::get(&id)? {
+        Some(date) => NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+            .with_context(|| format!("Invalid date `{}` for `{}` in `.todo-or-die`", date, id))?,
+        None => {
+            crate::marker::insert(&id, &today.to_string())?;
+            today
+        }
+    };
+
+    let deadline = first_seen
+        .checked_add_days(chrono::Days::new(input.days))
+        .context("Date out of range")?;
+
+    if deadline <= today {
+        Ok(Some(format!(
+            "`{}` was first compiled on {}, more than {} days ago. Time to act on this!",
+            id, first_seen, input.days
+        )))
+    } else {
+        Ok(None)
+    }
+}
+
+pub(crate) fn after_business_days(input: BusinessDays) -> Result<Option<String>> {
+    let from = input.from.to_naive_date();
+    let no_holidays = Holidays::Dates(Vec::new());
+    let mut calendar = input
+        .options
+        .holidays
+        .as_ref()
+        .unwrap_or(&no_holidays)
+        .calendar();
+
+    let mut deadline = from;
+    let mut remaining = input.days;
+    while remaining > 0 {
+        deadline = deadline.succ_opt().context("Date out of range")?;
+        if calendar.is_business_day(deadline)? {
+            remaining -= 1;
+        }
+    }
+
+    if deadline <= today(input.options.timezone)? {
+        Ok(Some(format!(
+            "{} business days since {} have passed. Time to act on this!",
+            input.days, from
+        )))
+    } else {
+        Ok(None)
+    }
+}
+
+pub(crate) fn recurring(input: Recurring) -> Result<Option<String>> {
+    let today = today(input.timezone)?;
+
+    let window_start = (0..input.for_days)
+        .filter_map(|days_ago| today.checked_sub_days(chrono::Days::new(days_ago)))
+        .find(|date| input.schedule.matches(*date));
+
+    if let Some(window_start) = window_start {
+        Ok(Some(format!(
+            "We're within {} days of {}, which matches `{}`. Time to act on this!",
+            input.for_days, window_start, input.schedule.expr
+        )))
+    } else {
+        Ok(None)
+    }
+}
+
+pub(crate) fn after_datetime(input: DateTimeInput) -> Result<Option<String>> {
+    let deadline = match input {
+        DateTimeInput::Rfc3339(deadline) => deadline.with_timezone(&Utc),
+        DateTimeInput::Components {
+            date,
+            hour,
+            minute,
+            second,
+            timezone,
+        } => {
+            let naive = date
+                .to_naive_date()
+                .and_hms_opt(hour, minute, second)
+                .context("Invalid time")?;
+
+            // times that happen twice because of daylight saving time use the first occurrence,
+            // and times skipped by it use the first time after the gap
+            let timezone = resolve_timezone(timezone)?;
+            (0..=24 * 60)
+                .filter_map(|minutes| naive.checked_add_signed(chrono::Duration::minutes(minutes)))
+                .find_map(|naive| timezone.from_local_datetime(&naive).earliest())
+                .with_context(|| format!("{} doesn't exist in the given timezone", naive))?
+                .with_timezone(&Utc)
+        }
+    };
+
+    if deadline <= crate::clock::now()? {
         Ok(Some(format!(
             "{} is now in the past. Time to act on this!",
-            input
+            deadline.to_rfc3339()
         )))
     } else {
         Ok(None)
     }
 }
 
+/// Today's date in the timezone the macro should use.
+fn today(timezone: Option<Tz>) -> Result<NaiveDate> {
+    Ok(crate::clock::now()?
+        .with_timezone(&resolve_timezone(timezone)?)
+        .date_naive())
+}
+
+/// The timezone given to the macro, otherwise `TODO_OR_DIE_TZ`, otherwise UTC.
+fn resolve_timezone(timezone: Option<Tz>) -> Result<Tz> {
+    match timezone {
+        Some(timezone) => Ok(timezone),
+        None => crate::clock::timezone(),
+    }
+}
+
+pub(crate) enum AfterDate {
+    Date(Input),
+    Escalation(Escalation),
+}
+
+/// `warn: date, die: date`
+pub(crate) struct Escalation {
+    warn: Date,
+    die: Date,
+    options: Options,
+}
+
+impl Parse for AfterDate {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        if !(input.peek(syn::Ident) && input.peek2(syn::token::Colon)) {
+            return input.parse().map(AfterDate::Date);
+        }
+
+        let warn = parse_labeled_date(input, "warn")?;
+        input.parse::<syn::token::Comma>()?;
+        let die_span = input.span();
+        let die = parse_labeled_date(input, "die")?;
+        if die.to_naive_date() <= warn.to_naive_date() {
+            return Err(syn::Error::new(
+                die_span,
+                "the `die` date must be after the `warn` date",
+            ));
+        }
+        let options = input.parse()?;
+
+        Ok(AfterDate::Escalation(Escalation { warn, die, options }))
+    }
+}
+
+/// Parse `label: date`, where the date is a string or a tuple.
+fn parse_labeled_date(input: syn::parse::ParseStream, label: &str) -> syn::Result<Date> {
+    let ident = input.parse::<syn::Ident>()?;
+    if ident != label {
+        return Err(syn::Error::new(
+            ident.span(),
+            format!("expected `{}`", label),
+        ));
+    }
+    input.parse::<syn::token::Colon>()?;
+    parse_date_arg(input)
+}
+
 pub(crate) struct Input {
-    year: i32,
-    month: u32,
-    day: u32,
+    date: Date,
+    options: Options,
 }
 
 impl Parse for Input {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let date = input.parse()?;
+        let options = input.parse()?;
+
+        Ok(Self { date, options })
+    }
+}
+
+/// Optional trailing arguments for macros with deadlines: a timezone, such as
+/// `"Europe/Stockholm"`, and `holidays = ...`, in any order.
+#[derive(Default)]
+struct Options {
+    timezone: Option<Tz>,
+    holidays: Option<Holidays>,
+}
+
+impl Options {
+    /// Move a deadline that falls on a weekend or holiday to the next business day, if holidays
+    /// were given.
+    fn roll_forward(&self, date: NaiveDate) -> Result<NaiveDate> {
+        match &self.holidays {
+            Some(holidays) => holidays.calendar().roll_forward(date),
+            None => Ok(date),
+        }
+    }
+}
+
+impl Parse for Options {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut options = Self::default();
+
+        while input.parse::<syn::token::Comma>().is_ok() && !input.is_empty() {
+            if input.peek(syn::LitStr) {
+                let lit = input.parse::<syn::LitStr>()?;
+                if options.timezone.is_some() {
+                    return Err(syn::Error::new(lit.span(), "duplicate timezone"));
+                }
+                options.timezone = Some(parse_timezone_lit(&lit)?);
+                continue;
+            }
+
+            let ident = input.parse::<syn::Ident>()?;
+            if ident != "holidays" {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    "expected a timezone or `holidays`",
+                ));
+            }
+            if options.holidays.is_some() {
+                return Err(syn::Error::new(ident.span(), "duplicate `holidays`"));
+            }
+            input.parse::<syn::token::Eq>()?;
+            options.holidays = Some(input.parse()?);
+        }
+
+        Ok(options)
+    }
+}
+
+/// A date that has been checked to exist.
+pub(crate) struct Date(NaiveDate);
+
+impl Date {
+    pub(crate) fn to_naive_date(&self) -> NaiveDate {
+        self.0
+    }
+}
+
+impl Parse for Date {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        if input.peek(syn::LitStr) {
+            let lit = input.parse::<syn::LitStr>()?;
+            let date = NaiveDate::parse_from_str(&lit.value(), "%Y-%m-%d").map_err(|err| {
+                syn::Error::new(
+                    lit.span(),
+                    format!(
+                        "invalid date, expected a date such as `2025-06-01`: {}",
+                        err
+                    ),
+                )
+            })?;
+
+            return Ok(Self(date));
+        }
+
+        let year_lit = input.parse::<syn::LitInt>()?;
+        let year = year_lit.base10_parse()?;
+        input.parse::<syn::token::Comma>()?;
+
+        let month_lit = input.parse::<syn::LitInt>()?;
+        let month = month_lit.base10_parse()?;
+        if !(1..=12).contains(&month) {
+            return Err(syn::Error::new(
+                month_lit.span(),
+                format!("invalid month {}, expected 1 to 12", month),
+            ));
+        }
+        input.parse::<syn::token::Comma>()?;
+
+        let day_lit = input.parse::<syn::LitInt>()?;
+        let day = day_lit.base10_parse()?;
+
+        let first_of_month = NaiveDate::from_ymd_opt(year, month, 1)
+            .ok_or_else(|| syn::Error::new(year_lit.span(), "year out of range"))?;
+        let days_in_month = days_in_month(first_of_month);
+        let date = first_of_month.with_day(day).ok_or_else(|| {
+            syn::Error::new(
+                day_lit.span(),
+                format!(
+                    "invalid day {}, {} {} only has {} days",
+                    day,
+                    first_of_month.format("%B"),
+                    year,
+                    days_in_month
+                ),
+            )
+        })?;
+
+        Ok(Self(date))
+    }
+}
+
+fn days_in_month(first_of_month: NaiveDate) -> u32 {
+    let next_month = first_of_month
+        .checked_add_months(chrono::Months::new(1))
+        .unwrap_or(NaiveDate::MAX);
+    next_month.signed_duration_since(first_of_month).num_days() as u32
+}
+
+/// Parse an optional trailing timezone, such as `, "Europe/Stockholm"`, and trailing comma.
+fn parse_timezone(input: syn::parse::ParseStream) -> syn::Result<Option<Tz>> {
+    if input.parse::<syn::token::Comma>().is_err() || !input.peek(syn::LitStr) {
+        return Ok(None);
+    }
+
+    let lit = input.parse::<syn::LitStr>()?;
+    let timezone = parse_timezone_lit(&lit)?;
+
+    input.parse::<syn::token::Comma>().ok();
+
+    Ok(Some(timezone))
+}
+
+fn parse_timezone_lit(lit: &syn::LitStr) -> syn::Result<Tz> {
+    lit.value().parse().map_err(|_| {
+        syn::Error::new(
+            lit.span(),
+            "unknown timezone, expected a name such as `Europe/Stockholm`",
+        )
+    })
+}
+
+pub(crate) struct DateRange {
+    start: Date,
+    end: Date,
+    timezone: Option<Tz>,
+}
+
+impl Parse for DateRange {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let start = parse_date_arg(input)?;
+        input.parse::<syn::token::Comma>()?;
+        let end_span = input.span();
+        let end = parse_date_arg(input)?;
+        if start.to_naive_date() > end.to_naive_date() {
+            return Err(syn::Error::new(
+                end_span,
+                "the end date must not be before the start date",
+            ));
+        }
+
+        let timezone = parse_timezone(input)?;
+
+        Ok(Self {
+            start,
+            end,
+            timezone,
+        })
+    }
+}
+
+pub(crate) struct DurationInput {
+    from: Date,
+    duration: Duration,
+    options: Options,
+}
+
+#[derive(Clone, Copy)]
+enum Duration {
+    Days(u64),
+    Weeks(u64),
+    Months(u32),
+}
+
+impl fmt::Display for Duration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Duration::Days(1) => write!(f, "1 day"),
+            Duration::Days(n) => write!(f, "{} days", n),
+            Duration::Weeks(1) => write!(f, "1 week"),
+            Duration::Weeks(n) => write!(f, "{} weeks", n),
+            Duration::Months(1) => write!(f, "1 month"),
+            Duration::Months(n) => write!(f, "{} months", n),
+        }
+    }
+}
+
+impl Parse for DurationInput {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let ident = input.parse::<syn::Ident>()?;
+        if ident != "from" {
+            return Err(syn::Error::new(ident.span(), "expected `from`"));
+        }
+        input.parse::<syn::token::Eq>()?;
+        let from = parse_date_arg(input)?;
+
+        input.parse::<syn::token::Comma>()?;
+
+        let ident = input.parse::<syn::Ident>()?;
+        input.parse::<syn::token::Eq>()?;
+        let amount = input.parse::<syn::LitInt>()?;
+        let duration = if ident == "days" {
+            Duration::Days(amount.base10_parse()?)
+        } else if ident == "weeks" {
+            Duration::Weeks(amount.base10_parse()?)
+        } else if ident == "months" {
+            Duration::Months(amount.base10_parse()?)
+        } else {
+            return Err(syn::Error::new(
+                ident.span(),
+                "expected one of `days`, `weeks`, or `months`",
+            ));
+        };
+
+        let options = input.parse()?;
+
+        Ok(Self {
+            from,
+            duration,
+            options,
+        })
+    }
+}
+
+pub(crate) struct Quarter {
+    year: i32,
+    quarter: u32,
+    /// The month the fiscal year starts in, from 1 to 12.
+    fiscal_start: u32,
+    options: Options,
+}
+
+impl Parse for Quarter {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let year = input.parse::<syn::LitInt>()?.base10_parse()?;
+        input.parse::<syn::token::Comma>()?;
+
+        let ident = input.parse::<syn::Ident>()?;
+        let quarter = match &*ident.to_string() {
+            "Q1" => 1,
+            "Q2" => 2,
+            "Q3" => 3,
+            "Q4" => 4,
+            _ => {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    "expected one of `Q1`, `Q2`, `Q3`, or `Q4`",
+                ))
+            }
+        };
+
+        let mut fiscal_start = 1;
+        let fork = input.fork();
+        fork.parse::<syn::token::Comma>().ok();
+        if fork
+            .parse::<syn::Ident>()
+            .is_ok_and(|ident| ident == "fiscal_start")
+        {
+            input.parse::<syn::token::Comma>()?;
+            input.parse::<syn::Ident>()?;
+            input.parse::<syn::token::Eq>()?;
+
+            let lit = input.parse::<syn::LitStr>()?;
+            fiscal_start = parse_month(&lit.value()).ok_or_else(|| {
+                syn::Error::new(
+                    lit.span(),
+                    "expected the name of a month, such as `February`",
+                )
+            })?;
+        }
+
+        let options = input.parse()?;
+
+        Ok(Self {
+            year,
+            quarter,
+            fiscal_start,
+            options,
+        })
+    }
+}
+
+/// Parse the name of a month, such as `February` or `feb`, into its number.
+fn parse_month(name: &str) -> Option<u32> {
+    const MONTHS: [&str; 12] = [
+        "january",
+        "february",
+        "march",
+        "april",
+        "may",
+        "june",
+        "july",
+        "august",
+        "september",
+        "october",
+        "november",
+        "december",
+    ];
+
+    let name = name.trim().to_lowercase();
+    if name.len() < 3 {
+        return None;
+    }
+
+    MONTHS
+        .iter()
+        .position(|month| month.starts_with(&name))
+        .map(|idx| idx as u32 + 1)
+}
+
+pub(crate) struct Week {
+    /// The Monday the ISO week starts on.
+    start: NaiveDate,
+    options: Options,
+}
+
+impl Parse for Week {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
         let year = input.parse::<syn::LitInt>()?.base10_parse()?;
         input.parse::<syn::token::Comma>()?;
+        let week_lit = input.parse::<syn::LitInt>()?;
+        let week = week_lit.base10_parse()?;
+
+        let start =
+            NaiveDate::from_isoywd_opt(year, week, chrono::Weekday::Mon).ok_or_else(|| {
+                syn::Error::new(
+                    week_lit.span(),
+                    format!("{} doesn't have an ISO week {}", year, week),
+                )
+            })?;
+
+        let options = input.parse()?;
+
+        Ok(Self { start, options })
+    }
+}
+
+pub(crate) struct Sprint {
+    sprint: u64,
+    options: Options,
+}
+
+impl Parse for Sprint {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let sprint = input.parse::<syn::LitInt>()?.base10_parse()?;
+        let options = input.parse()?;
+
+        Ok(Self { sprint, options })
+    }
+}
+
+pub(crate) struct DiesIn {
+    id: Option<String>,
+    days: u64,
+}
+
+impl DiesIn {
+    /// Use the `id` given as a modifier, such as in `dies_in!(days = 90, id = "retry-shim")`,
+    /// if the input doesn't have one.
+    pub(crate) fn or_id(mut self, id: Option<&str>) -> Self {
+        if self.id.is_none() {
+            self.id = id.map(ToOwned::to_owned);
+        }
+        self
+    }
+}
+
+impl Parse for DiesIn {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut id = None;
+        let mut days = None;
+
+        while !input.is_empty() {
+            let ident = input.parse::<syn::Ident>()?;
+            input.parse::<syn::token::Eq>()?;
+
+            if ident == "days" {
+                days = Some(input.parse::<syn::LitInt>()?.base10_parse()?);
+            } else if ident == "id" {
+                let lit = input.parse::<syn::LitStr>()?;
+                let value = lit.value();
+                if !crate::modifiers::is_valid_id(&value) {
+                    return Err(syn::Error::new(
+                        lit.span(),
+                        "`id` must be non-empty and only contain letters, numbers, `-`, and `_`",
+                    ));
+                }
+                id = Some(value);
+            } else {
+                return Err(syn::Error::new(ident.span(), "expected `days` or `id`"));
+            }
+
+            if input.parse::<syn::token::Comma>().is_err() {
+                break;
+            }
+        }
+
+        let missing = |name| syn::Error::new(input.span(), format!("missing `{}`", name));
+        Ok(Self {
+            id,
+            days: days.ok_or_else(|| missing("days"))?,
+        })
+    }
+}
+
+pub(crate) struct BusinessDays {
+    from: Date,
+    days: u64,
+    options: Options,
+}
+
+impl Parse for BusinessDays {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let ident = input.parse::<syn::Ident>()?;
+        if ident != "from" {
+            return Err(syn::Error::new(ident.span(), "expected `from`"));
+        }
+        input.parse::<syn::token::Eq>()?;
+        let from = parse_date_arg(input)?;
+
+        input.parse::<syn::token::Comma>()?;
+
+        let ident = input.parse::<syn::Ident>()?;
+        if ident != "days" {
+            return Err(syn::Error::new(ident.span(), "expected `days`"));
+        }
+        input.parse::<syn::token::Eq>()?;
+        let days = input.parse::<syn::LitInt>()?.base10_parse()?;
+
+        let options = input.parse()?;
+
+        Ok(Self {
+            from,
+            days,
+            options,
+        })
+    }
+}
+
+pub(crate) struct Recurring {
+    schedule: Schedule,
+    for_days: u64,
+    timezone: Option<Tz>,
+}
+
+impl Parse for Recurring {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let lit = input.parse::<syn::LitStr>()?;
+        let schedule = Schedule::parse(&lit.value()).map_err(|err| {
+            syn::Error::new(lit.span(), format!("invalid cron expression: {}", err))
+        })?;
 
-        let month = input.parse::<syn::LitInt>()?.base10_parse()?;
         input.parse::<syn::token::Comma>()?;
 
-        let day = input.parse::<syn::LitInt>()?.base10_parse()?;
+        let ident = input.parse::<syn::Ident>()?;
+        if ident != "for_days" {
+            return Err(syn::Error::new(ident.span(), "expected `for_days`"));
+        }
+        input.parse::<syn::token::Eq>()?;
+        let lit = input.parse::<syn::LitInt>()?;
+        let for_days = lit.base10_parse()?;
+        if for_days == 0 {
+            return Err(syn::Error::new(lit.span(), "`for_days` must be at least 1"));
+        }
 
-        input.parse::<syn::token::Comma>().ok();
+        let timezone = parse_timezone(input)?;
 
-        Ok(Self { year, month, day })
+        Ok(Self {
+            schedule,
+            for_days,
+            timezone,
+        })
     }
 }
 
-/// ```compile_fail
-/// todo_or_die::after_date!(1990, 01, 01);
-/// ```
+/// The day part of a cron expression like `0 0 1 1,7 *`.
 ///
-/// ```
-/// todo_or_die::after_date!(3000, 01, 01);
+/// The minute and hour fields are validated but otherwise ignored since windows are whole days.
+struct Schedule {
+    expr: String,
+    days_of_month: Vec<u32>,
+    months: Vec<u32>,
+    days_of_week: Vec<u32>,
+    day_of_month_restricted: bool,
+    day_of_week_restricted: bool,
+}
+
+impl Schedule {
+    fn parse(expr: &str) -> Result<Self, String> {
+        let fields = expr.split_whitespace().collect::<Vec<_>>();
+        let (minute, hour, day_of_month, month, day_of_week) = match fields[..] {
+            [minute, hour, day_of_month, month, day_of_week] => {
+                (minute, hour, day_of_month, month, day_of_week)
+            }
+            _ => return Err("expected 5 fields".to_owned()),
+        };
+
+        parse_cron_field(minute, 0, 59)?;
+        parse_cron_field(hour, 0, 23)?;
+
+        Ok(Self {
+            expr: expr.to_owned(),
+            days_of_month: parse_cron_field(day_of_month, 1, 31)?,
+            months: parse_cron_field(month, 1, 12)?,
+            // both 0 and 7 mean Sunday
+            days_of_week: parse_cron_field(day_of_week, 0, 7)?
+                .into_iter()
+                .map(|day| day % 7)
+                .collect(),
+            day_of_month_restricted: day_of_month != "*",
+            day_of_week_restricted: day_of_week != "*",
+        })
+    }
+
+    fn matches(&self, date: NaiveDate) -> bool {
+        if !self.months.contains(&date.month()) {
+            return false;
+        }
+
+        let day_of_month = self.days_of_month.contains(&date.day());
+        let day_of_week = self
+            .days_of_week
+            .contains(&date.weekday().num_days_from_sunday());
+
+        // like cron, if both are restricted a day matching either is enough
+        if self.day_of_month_restricted && self.day_of_week_restricted {
+            day_of_month || day_of_week
+        } else {
+            day_of_month && day_of_week
+        }
+    }
+}
+
+/// Parse a cron field such as `*`, `1,7`, `1-5`, or `*/15` into the values it matches.
+fn parse_cron_field(field: &str, min: u32, max: u32) -> Result<Vec<u32>, String> {
+    let mut values = Vec::new();
+
+    for part in field.split(',') {
+        let mut range_and_step = part.splitn(2, '/');
+        let range = range_and_step.next().unwrap_or_default();
+        let step = match range_and_step.next() {
+            Some(step) => step
+                .parse::<u32>()
+                .ok()
+                .filter(|step| *step > 0)
+                .ok_or_else(|| format!("invalid step `{}`", step))?,
+            None => 1,
+        };
+
+        let parse_value = |value: &str| {
+            value
+                .parse::<u32>()
+                .ok()
+                .filter(|value| (min..=max).contains(value))
+                .ok_or_else(|| format!("`{}` must be a number from {} to {}", value, min, max))
+        };
+
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else {
+            let mut bounds = range.splitn(2, '-');
+            let start = parse_value(bounds.next().unwrap_or_default())?;
+            match bounds.next() {
+                Some(end) => (start, parse_value(end)?),
+                None if step > 1 => (start, max),
+                None => (start, start),
+            }
+        };
+
+        if start > end {
+            return Err(format!("invalid range `{}`", range));
+        }
+
+        values.extend((start..=end).step_by(step as usize));
+    }
+
+    Ok(values)
+}
+
+/// Parse either a date string or a `(year, month, day)` tuple.
+pub(crate) fn parse_date_arg(input: syn::parse::ParseStream) -> syn::Result<Date> {
+    if input.peek(syn::LitStr) {
+        return input.parse();
+    }
+
+    let content;
+    syn::parenthesized!(content in input);
+    let date = content.parse()?;
+    content.parse::<syn::token::Comma>().ok();
+
+    Ok(date)
+}
+
+pub(crate) enum DateTimeInput {
+    Rfc3339(DateTime<FixedOffset>),
+    Components {
+        date: Date,
+        hour: u32,
+        minute: u32,
+        second: u32,
+        timezone: Option<Tz>,
+    },
+}
+
+impl Parse for DateTimeInput {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        if input.peek(syn::LitStr) {
+            let lit = input.parse::<syn::LitStr>()?;
+            let deadline = DateTime::parse_from_rfc3339(&lit.value()).map_err(|err| {
+                syn::Error::new(
+                    lit.span(),
+                    format!("invalid RFC 3339 date and time: {}", err),
+                )
+            })?;
+            input.parse::<syn::token::Comma>().ok();
+            return Ok(Self::Rfc3339(deadline));
+        }
+
+        let date = input.parse()?;
+        input.parse::<syn::token::Comma>()?;
+
+        let hour = parse_time_component(input, 24, "hour")?;
+        input.parse::<syn::token::Comma>()?;
+
+        let minute = parse_time_component(input, 60, "minute")?;
+        input.parse::<syn::token::Comma>()?;
+
+        let second = parse_time_component(input, 60, "second")?;
+
+        let timezone = parse_timezone(input)?;
+
+        Ok(Self::Components {
+            date,
+            hour,
+            minute,
+            second,
+            timezone,
+        })
+    }
+}
+
+/// Parse an hour, minute, or second that must be less than `max`.
+fn parse_time_component(input: syn::parse::ParseStream, max: u32, name: &str) -> syn::Result<u32> {
+    let lit = input.parse::<syn::LitInt>()?;
+    let value = lit.base10_parse()?;
+    if value >= max {
+        return Err(syn::Error::new(
+            lit.span(),
+            format!("{} must be less than {}", name, max),
+        ));
+    }
+    Ok(value)
+}
+
+/// ```compile_fail
+/// todo_or_die::after_date!(1990, 01, 01);
+/// ```
+///
+/// ```
+/// todo_or_die::after_date!(3000, 01, 01);
+/// ```
+///
+/// with a timezone
+/// ```compile_fail
+/// todo_or_die::after_date!(1990, 01, 01, "Pacific/Auckland");
+/// ```
+///
+/// ```
+/// todo_or_die::after_date!(3000, 01, 01, "Pacific/Auckland",);
+/// ```
+///
+/// unknown timezone
+/// ```compile_fail
+/// todo_or_die::after_date!(3000, 01, 01, "Pacific/Atlantis");
+/// ```
+///
+/// date string
+/// ```compile_fail
+/// todo_or_die::after_date!("1990-01-01");
+/// ```
+///
+/// ```
+/// todo_or_die::after_date!("3000-01-01", "Pacific/Auckland");
+/// ```
+///
+/// invalid date string
+/// ```compile_fail
+/// todo_or_die::after_date!("3000-13-01");
+/// ```
+///
+/// ```compile_fail
+/// todo_or_die::after_date!("01/01/3000");
+/// ```
+///
+/// warn, then die
+/// ```
+/// todo_or_die::after_date!(warn: (1990, 01, 01), die: (3000, 01, 01));
+/// ```
+///
+/// ```compile_fail
+/// todo_or_die::after_date!(warn: "1990-01-01", die: "1990-02-01", "Europe/Stockholm");
+/// ```
+///
+/// ```
+/// todo_or_die::after_date!(warn: (3000, 01, 01), die: (3000, 02, 01), holidays = []);
+/// ```
+///
+/// warnings are regular warnings
+/// ```compile_fail
+/// #![deny(warnings)]
+/// todo_or_die::after_date!(warn: (1990, 01, 01), die: (3000, 01, 01));
+/// ```
+///
+/// ```compile_fail
+/// todo_or_die::after_date!(warn: (3000, 02, 01), die: (3000, 01, 01));
+/// ```
+///
+/// ```compile_fail
+/// todo_or_die::after_date!(die: (3000, 02, 01), warn: (3000, 01, 01));
+/// ```
+///
+/// invalid dates
+/// ```compile_fail
+/// todo_or_die::after_date!(3000, 02, 30);
+/// ```
+///
+/// ```compile_fail
+/// todo_or_die::after_date!(3000, 13, 01);
+/// ```
+///
+/// ```compile_fail
+/// todo_or_die::after_date!(3000, 04, 00);
+/// ```
+///
+/// 3000 isn't a leap year but 3004 is
+/// ```compile_fail
+/// todo_or_die::after_date!(3000, 02, 29);
+/// ```
+///
+/// ```
+/// todo_or_die::after_date!(3004, 02, 29);
+/// ```
+///
+/// # `before_date`
+///
+/// ```compile_fail
+/// todo_or_die::before_date!(3000, 01, 01);
+/// ```
+///
+/// ```
+/// todo_or_die::before_date!(1990, 01, 01);
+/// ```
+///
+/// # `between_dates`
+///
+/// ```compile_fail
+/// todo_or_die::between_dates!((1990, 01, 01), (3000, 01, 01));
+/// ```
+///
+/// ```
+/// todo_or_die::between_dates!((1990, 01, 01), (1990, 12, 31));
+/// ```
+///
+/// ```
+/// todo_or_die::between_dates!((3000, 01, 01), (3000, 12, 31));
+/// ```
+///
+/// with a timezone
+/// ```compile_fail
+/// todo_or_die::between_dates!((1990, 01, 01), (3000, 01, 01), "Europe/Stockholm");
+/// ```
+///
+/// date strings
+/// ```compile_fail
+/// todo_or_die::between_dates!("1990-01-01", "3000-01-01");
+/// ```
+///
+/// ```
+/// todo_or_die::between_dates!("3000-01-01", (3000, 12, 31), "Europe/Stockholm");
+/// ```
+///
+/// end before start
+/// ```compile_fail
+/// todo_or_die::between_dates!((3000, 01, 01), (1990, 01, 01));
+/// ```
+///
+/// # `after_duration`
+///
+/// ```compile_fail
+/// todo_or_die::after_duration!(from = (1990, 01, 01), months = 6);
+/// ```
+///
+/// ```
+/// todo_or_die::after_duration!(from = (3000, 01, 01), days = 1);
+/// ```
+///
+/// ```compile_fail
+/// todo_or_die::after_duration!(from = "1990-01-01", weeks = 2, "Europe/Stockholm");
+/// ```
+///
+/// ```
+/// todo_or_die::after_duration!(from = "2000-01-01", months = 12000);
+/// ```
+///
+/// unknown unit
+/// ```compile_fail
+/// todo_or_die::after_duration!(from = (1990, 01, 01), years = 1);
+/// ```
+///
+/// # `after_quarter`
+///
+/// ```compile_fail
+/// todo_or_die::after_quarter!(1990, Q2);
+/// ```
+///
+/// ```
+/// todo_or_die::after_quarter!(3000, Q1);
+/// ```
+///
+/// Q4 of fiscal year 1990 starts in November 1990
+/// ```compile_fail
+/// todo_or_die::after_quarter!(1990, Q4, fiscal_start = "February", "Europe/Stockholm");
+/// ```
+///
+/// Q4 of fiscal year 2999 starts in January 3000
+/// ```
+/// todo_or_die::after_quarter!(2999, Q4, fiscal_start = "Apr");
+/// ```
+///
+/// unknown quarter
+/// ```compile_fail
+/// todo_or_die::after_quarter!(1990, Q5);
+/// ```
+///
+/// unknown month
+/// ```compile_fail
+/// todo_or_die::after_quarter!(1990, Q1, fiscal_start = "Smarch");
+/// ```
+///
+/// # `after_week`
+///
+/// ```compile_fail
+/// todo_or_die::after_week!(1990, 14);
+/// ```
+///
+/// ```
+/// todo_or_die::after_week!(3000, 1, "Europe/Stockholm");
+/// ```
+///
+/// week 1 of 2026 starts on December 29th 2025
+/// ```compile_fail
+/// todo_or_die::after_week!(2026, 1);
+/// ```
+///
+/// 3001 has 53 weeks but 3002 doesn't
+/// ```
+/// todo_or_die::after_week!(3001, 53);
+/// ```
+///
+/// ```compile_fail
+/// todo_or_die::after_week!(3002, 53);
+/// ```
+///
+/// ```compile_fail
+/// todo_or_die::after_week!(3000, 0);
+/// ```
+///
+/// # `after_sprint`
+///
+/// uses the sprints configured in this crate's `Cargo.toml`
+/// ```compile_fail
+/// todo_or_die::after_sprint!(1);
+/// ```
+///
+/// ```
+/// todo_or_die::after_sprint!(100000, "Europe/Stockholm");
+/// ```
+///
+/// ```compile_fail
+/// todo_or_die::after_sprint!(0);
+/// ```
+///
+/// # `dies_in`
+///
+/// ```compile_fail
+/// todo_or_die::dies_in!(days = 0, id = "dies-in-doctest-now");
+/// ```
+///
+/// ```
+/// todo_or_die::dies_in!(days = 100000, id = "dies-in-doctest-later");
+/// ```
+///
+/// missing `id`
+/// ```compile_fail
+/// todo_or_die::dies_in!(days = 90);
+/// ```
+///
+/// invalid `id`
+/// ```compile_fail
+/// todo_or_die::dies_in!(days = 90, id = "not valid");
+/// ```
+///
+/// `id` can come first
+/// ```
+/// todo_or_die::dies_in!(id = "dies-in-doctest-later", days = 100000);
+/// ```
+///
+/// # `after_business_days`
+///
+/// ```compile_fail
+/// todo_or_die::after_business_days!(from = (1990, 01, 01), days = 10);
+/// ```
+///
+/// ```
+/// todo_or_die::after_business_days!(from = "3000-01-01", days = 10, "Europe/Stockholm");
+/// ```
+///
+/// with holidays
+/// ```compile_fail
+/// todo_or_die::after_business_days!(
+///     from = (1990, 12, 20),
+///     days = 5,
+///     holidays = ["1990-12-24", (1990, 12, 25)],
+/// );
+/// ```
+///
+/// invalid holiday
+/// ```compile_fail
+/// todo_or_die::after_business_days!(from = (3000, 01, 01), days = 5, holidays = ["christmas"]);
+/// ```
+///
+/// # `recurring`
+///
+/// every day
+/// ```compile_fail
+/// todo_or_die::recurring!("0 0 * * *", for_days = 1);
+/// ```
+///
+/// every Monday through Sunday, as a step
+/// ```compile_fail
+/// todo_or_die::recurring!("0 0 * * 0-6/1", for_days = 1);
+/// ```
+///
+/// February 30th never happens
+/// ```
+/// todo_or_die::recurring!("0 0 30 2 *", for_days = 14, "Europe/Stockholm");
+/// ```
+///
+/// wrong number of fields
+/// ```compile_fail
+/// todo_or_die::recurring!("0 0 1 1", for_days = 14);
+/// ```
+///
+/// out of range
+/// ```compile_fail
+/// todo_or_die::recurring!("0 0 1 13 *", for_days = 14);
+/// ```
+///
+/// empty window
+/// ```compile_fail
+/// todo_or_die::recurring!("0 0 * * *", for_days = 0);
+/// ```
+///
+/// # `after_datetime`
+///
+/// ```compile_fail
+/// todo_or_die::after_datetime!(1990, 01, 01, 12, 0, 0);
+/// ```
+///
+/// ```
+/// todo_or_die::after_datetime!(3000, 01, 01, 12, 0, 0);
+/// ```
+///
+/// with a timezone
+/// ```compile_fail
+/// todo_or_die::after_datetime!(1990, 01, 01, 12, 0, 0, "Europe/Stockholm");
+/// ```
+///
+/// ```
+/// todo_or_die::after_datetime!(3000, 01, 01, 12, 0, 0, "Europe/Stockholm");
+/// ```
+///
+/// unknown timezone
+/// ```compile_fail
+/// todo_or_die::after_datetime!(3000, 01, 01, 12, 0, 0, "Europe/Atlantis");
+/// ```
+///
+/// invalid time
+/// ```compile_fail
+/// todo_or_die::after_datetime!(3000, 01, 01, 25, 0, 0);
+/// ```
+///
+/// skipped by daylight saving time
+/// ```compile_fail
+/// todo_or_die::after_datetime!(2021, 03, 28, 2, 30, 0, "Europe/Stockholm");
+/// ```
+///
+/// invalid RFC 3339
+/// ```compile_fail
+/// todo_or_die::after_datetime!("3000-01-01 12:00");
+/// ```
+///
+/// RFC 3339
+/// ```compile_fail
+/// todo_or_die::after_datetime!("1990-01-01T12:00:00+01:00");
+/// ```
+///
+/// ```
+/// todo_or_die::after_datetime!("3000-01-01T12:00:00Z");
 /// ```
 #[allow(dead_code)]
 fn tests() {}
@@ -0,0 +1,165 @@
+use anyhow::{Context as _, Result};
+use chrono::{DateTime, TimeZone as _, Utc};
+use rustls::Session as _;
+use std::{
+    net::{TcpStream, ToSocketAddrs as _},
+    sync::Arc,
+    time::Duration,
+};
+use syn::parse::Parse;
+
+pub(crate) fn cert_expires(input: CertExpires) -> Result<Option<String>> {
+    let expires_at = leaf_certificate_expiry(&input.host, input.port)?;
+    let now = crate::clock::now()?;
+    let days_left = (expires_at - now).num_days();
+    let expiry_date = expires_at
+        .with_timezone(&crate::clock::timezone()?)
+        .date_naive();
+
+    if expires_at <= now {
+        Ok(Some(format!(
+            "The certificate for {} expired on {}. Time to act on this!",
+            input.host, expiry_date
+        )))
+    } else if days_left < input.days_before.into() {
+        Ok(Some(format!(
+            "The certificate for {} expires on {}, in {} days. Time to act on this!",
+            input.host, expiry_date, days_left
+        )))
+    } else {
+        Ok(None)
+    }
+}
+
+/// When the certificate the server presents expires.
+fn leaf_certificate_expiry(host: &str, port: u16) -> Result<DateTime<Utc>> {
+    crate::http::ensure_online()?;
+
+    let mut config = rustls::ClientConfig::new();
+    // we only want to look at the certificate and never send anything, so there is no need to
+    // verify it. That also lets us report certificates that have already expired
+    config
+        .dangerous()
+        .set_certificate_verifier(Arc::new(AcceptAnyCertificate));
+
+    let dns_name = webpki::DNSNameRef::try_from_ascii_str(host)
+        .map_err(|_| anyhow::anyhow!("Invalid host name `{}`", host))?;
+    let mut session = rustls::ClientSession::new(&Arc::new(config), dns_name);
+
+    let addr = (host, port)
+        .to_socket_addrs()
+        .with_context(|| format!("Failed to resolve {}", host))?
+        .next()
+        .with_context(|| format!("Failed to resolve {}", host))?;
+    let mut socket = TcpStream::connect_timeout(&addr, Duration::from_secs(10))
+        .with_context(|| format!("Failed to connect to {}:{}", host, port))?;
+    socket.set_read_timeout(Some(Duration::from_secs(10)))?;
+    socket.set_write_timeout(Some(Duration::from_secs(10)))?;
+
+    while session.is_handshaking() {
+        session
+            .complete_io(&mut socket)
+            .with_context(|| format!("TLS handshake with {}:{} failed", host, port))?;
+    }
+
+    let certificates = session.get_peer_certificates().unwrap_or_default();
+    let leaf = certificates
+        .first()
+        .with_context(|| format!("{} didn't present a certificate", host))?;
+
+    let (_, certificate) =
+        x509_parser::parse_x509_certificate(&leaf.0).context("Failed to parse certificate")?;
+    let not_after = certificate.validity().not_after.timestamp();
+
+    Utc.timestamp_opt(not_after, 0)
+        .single()
+        .context("Certificate expiry out of range")
+}
+
+struct AcceptAnyCertificate;
+
+impl rustls::ServerCertVerifier for AcceptAnyCertificate {
+    fn verify_server_cert(
+        &self,
+        _roots: &rustls::RootCertStore,
+        _presented_certs: &[rustls::Certificate],
+        _dns_name: webpki::DNSNameRef<'_>,
+        _ocsp_response: &[u8],
+    ) -> Result<rustls::ServerCertVerified, rustls::TLSError> {
+        Ok(rustls::ServerCertVerified::assertion())
+    }
+}
+
+pub(crate) struct CertExpires {
+    host: String,
+    port: u16,
+    days_before: u32,
+}
+
+impl Parse for CertExpires {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let lit = input.parse::<syn::LitStr>()?;
+        let value = lit.value();
+
+        let (host, port) = match value.rsplit_once(':') {
+            Some((host, port)) => {
+                let port = port
+                    .parse()
+                    .map_err(|_| syn::Error::new(lit.span(), "invalid port"))?;
+                (host.to_owned(), port)
+            }
+            None => (value, 443),
+        };
+
+        if webpki::DNSNameRef::try_from_ascii_str(&host).is_err() {
+            return Err(syn::Error::new(
+                lit.span(),
+                "expected a host name, such as `api.example.com`",
+            ));
+        }
+
+        let mut days_before = 30;
+        if input.parse::<syn::token::Comma>().is_ok() && !input.is_empty() {
+            let ident = input.parse::<syn::Ident>()?;
+            if ident != "days_before" {
+                return Err(syn::Error::new(ident.span(), "expected `days_before`"));
+            }
+            input.parse::<syn::token::Eq>()?;
+            days_before = input.parse::<syn::LitInt>()?.base10_parse()?;
+            input.parse::<syn::token::Comma>().ok();
+        }
+
+        Ok(Self {
+            host,
+            port,
+            days_before,
+        })
+    }
+}
+
+/// ```
+/// todo_or_die::cert_expires!("github.com", days_before = 0);
+/// ```
+///
+/// ```compile_fail
+/// todo_or_die::cert_expires!("github.com", days_before = 100000);
+/// ```
+///
+/// ```compile_fail
+/// todo_or_die::cert_expires!("expired.badssl.com");
+/// ```
+///
+/// ```
+/// todo_or_die::cert_expires!("github.com:443",);
+/// ```
+///
+/// invalid host
+/// ```compile_fail
+/// todo_or_die::cert_expires!("https://github.com");
+/// ```
+///
+/// ```compile_fail
+/// todo_or_die::cert_expires!("github.com", days = 30);
+/// ```
+#[allow(dead_code)]
+fn tests() {}